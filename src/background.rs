@@ -0,0 +1,72 @@
+mod constant;
+mod environmentmap;
+mod gradient;
+mod sky;
+mod texture;
+use crate::{Colour, Vec3};
+pub use constant::ConstantBackground;
+pub use environmentmap::EnvironmentMap;
+pub use gradient::GradientBackground;
+pub use sky::Sky;
+use std::{f64::consts, fmt::Debug};
+pub use texture::TextureBackground;
+
+/**
+ * Trait for the light arriving from infinitely far away, seen by rays that miss every object.
+ */
+pub trait Background: Debug + Send + Sync {
+    /**
+     * Returns the light seen looking in `direction`, which need not be a unit vector.
+     */
+    #[must_use]
+    fn value(&self, direction: Vec3) -> Colour;
+
+    /**
+     * Maps the uniform sample `sample` to a direction, for sampling the light cast by the
+     * background directly.  Returns the direction, as a unit vector, the light seen looking in
+     * that direction, and the probability density of choosing it, per unit solid angle.  Returns
+     * `None` if the background is not sampled directly.
+     */
+    #[must_use]
+    fn sample(&self, _sample: (f64, f64)) -> Option<(Vec3, Colour, f64)> {
+        None
+    }
+
+    /**
+     * Returns the probability density, per unit solid angle, of `sample` choosing `direction`.
+     */
+    #[must_use]
+    fn pdf(&self, _direction: Vec3) -> f64 {
+        0.0
+    }
+}
+
+/**
+ * Maps a direction to latitude–longitude coordinates in [0, 1)², with `v` increasing downwards
+ * from straight up (+y), and `u` = 0.5 looking along −z, before rotating by `rotation` radians
+ * about the vertical axis.  Also returns the sine of the angle from straight up.
+ */
+#[must_use]
+fn direction_to_lat_long(direction: Vec3, rotation: f64) -> (f64, f64, f64) {
+    let direction = direction.unit();
+    let phi = direction.x().atan2(-direction.z()) - rotation;
+    let theta = direction.y().clamp(-1.0, 1.0).acos();
+
+    let u = (0.5 + phi / consts::TAU).rem_euclid(1.0);
+    let v = theta / consts::PI;
+    (u, v, theta.sin())
+}
+
+/**
+ * Maps latitude–longitude coordinates to a unit vector; the inverse of `direction_to_lat_long`.
+ */
+#[must_use]
+fn lat_long_to_direction(u: f64, v: f64, rotation: f64) -> (Vec3, f64) {
+    let phi = consts::TAU * (u - 0.5) + rotation;
+    let (sin_theta, cos_theta) = (consts::PI * v).sin_cos();
+    let (sin_phi, cos_phi) = phi.sin_cos();
+    (
+        Vec3(sin_theta * sin_phi, cos_theta, -sin_theta * cos_phi),
+        sin_theta,
+    )
+}
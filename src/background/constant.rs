@@ -0,0 +1,35 @@
+use super::Background;
+use crate::{Colour, Vec3};
+use std::sync::Arc;
+
+/**
+ * Type for representing backgrounds of a single colour.
+ */
+#[derive(Debug)]
+pub struct ConstantBackground {
+    value: Colour,
+}
+
+impl ConstantBackground {
+    #[must_use]
+    pub fn new(value: Colour) -> Self {
+        Self { value }
+    }
+
+    #[must_use]
+    pub fn new_background(value: Colour) -> Arc<dyn Background> {
+        Arc::new(Self::new(value))
+    }
+}
+
+impl From<Colour> for Arc<dyn Background> {
+    fn from(colour: Colour) -> Self {
+        ConstantBackground::new_background(colour)
+    }
+}
+
+impl Background for ConstantBackground {
+    fn value(&self, _direction: Vec3) -> Colour {
+        self.value
+    }
+}
@@ -0,0 +1,301 @@
+use super::{direction_to_lat_long, lat_long_to_direction, Background};
+use crate::{Colour, Vec3};
+use image::{codecs::hdr::HdrDecoder, io::Reader, ImageFormat};
+use std::{error::Error, f64::consts, fs::File, io::BufReader, path::Path, sync::Arc};
+
+/**
+ * Type for backgrounds given by an equirectangular (latitude–longitude) image.
+ *
+ * The top row of the image is straight up (+y), and the centre of the image looks along −z.
+ * Directions are importance-sampled in proportion to the luminance of the image, so that small,
+ * bright features such as the sun are found by direct lighting.
+ */
+#[derive(Debug)]
+pub struct EnvironmentMap {
+    width: usize,
+    height: usize,
+    data: Box<[Colour]>,
+    rotation: f64,
+
+    /**
+     * Cumulative distribution of the rows, normalized to 1.
+     */
+    marginal_cdf: Box<[f64]>,
+
+    /**
+     * Cumulative distributions of the pixels within each row, normalized to 1, with `width + 1`
+     * entries per row.
+     */
+    conditional_cdf: Box<[f64]>,
+
+    /**
+     * Sum of the sampling weights of all the pixels.
+     */
+    total_weight: f64,
+}
+
+impl EnvironmentMap {
+    /**
+     * Loads an environment map.
+     *
+     * # Parameters
+     *
+     * * `filename` is the name of the image file.  Radiance `.hdr` and OpenEXR `.exr` files are
+     *   read with their full dynamic range; other formats are read as 8-bit images scaled to
+     *   [0, 1].
+     * * `rotation` is the rotation of the map about the vertical axis, in degrees.
+     * * `intensity` scales the brightness of the map.
+     */
+    pub fn new(
+        filename: impl AsRef<Path>,
+        rotation: f64,
+        intensity: f64,
+    ) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        assert!(intensity >= 0.0);
+
+        let filename = filename.as_ref();
+        let (width, height, data) = match ImageFormat::from_path(filename).ok() {
+            Some(ImageFormat::Hdr) => load_hdr(filename)?,
+            _ if is_exr(filename) => load_exr(filename)?,
+            _ => load_ldr(filename)?,
+        };
+        if width == 0 || height == 0 {
+            return Err(format!("environment map file “{}” is empty", filename.display()).into());
+        }
+        let data: Box<[Colour]> = data.into_iter().map(|c| intensity * c).collect();
+
+        // Weight each pixel by its luminance and by the solid angle it covers.
+        let mut conditional_cdf = vec![0.0; height * (width + 1)];
+        let mut marginal_cdf = vec![0.0; height + 1];
+        for j in 0..height {
+            let sin_theta = (consts::PI * (j as f64 + 0.5) / height as f64).sin();
+            let row = &mut conditional_cdf[j * (width + 1)..(j + 1) * (width + 1)];
+            for i in 0..width {
+                row[i + 1] = row[i] + data[j * width + i].luminance().max(0.0) * sin_theta;
+            }
+            marginal_cdf[j + 1] = marginal_cdf[j] + row[width];
+            if row[width] > 0.0 {
+                let row_weight = row[width];
+                row.iter_mut().for_each(|c| *c /= row_weight);
+            }
+        }
+        let total_weight = marginal_cdf[height];
+        if total_weight > 0.0 {
+            marginal_cdf.iter_mut().for_each(|c| *c /= total_weight);
+        }
+
+        Ok(Self {
+            width,
+            height,
+            data,
+            rotation: rotation.to_radians(),
+            marginal_cdf: marginal_cdf.into_boxed_slice(),
+            conditional_cdf: conditional_cdf.into_boxed_slice(),
+            total_weight,
+        })
+    }
+
+    pub fn new_background(
+        filename: impl AsRef<Path>,
+        rotation: f64,
+        intensity: f64,
+    ) -> Result<Arc<dyn Background>, Box<dyn Error + Send + Sync>> {
+        Ok(Arc::new(Self::new(filename, rotation, intensity)?))
+    }
+
+    /**
+     * Returns the index of the pixel containing image coordinates `(u, v)`.
+     */
+    #[must_use]
+    fn pixel_index(&self, u: f64, v: f64) -> usize {
+        let i = ((u * self.width as f64) as usize).min(self.width - 1);
+        let j = ((v * self.height as f64) as usize).min(self.height - 1);
+        j * self.width + i
+    }
+
+    /**
+     * Returns the probability density of sampling the pixel at `index`, per unit area of image
+     * coordinates.
+     */
+    #[must_use]
+    fn pixel_pdf(&self, index: usize) -> f64 {
+        let (i, j) = (index % self.width, index / self.width);
+        let row = &self.conditional_cdf[j * (self.width + 1)..(j + 1) * (self.width + 1)];
+        let row_probability = self.marginal_cdf[j + 1] - self.marginal_cdf[j];
+        let probability = row_probability * (row[i + 1] - row[i]);
+        probability * (self.width * self.height) as f64
+    }
+}
+
+impl Background for EnvironmentMap {
+    fn value(&self, direction: Vec3) -> Colour {
+        let (u, v, _) = direction_to_lat_long(direction, self.rotation);
+        self.data[self.pixel_index(u, v)]
+    }
+
+    fn sample(&self, sample: (f64, f64)) -> Option<(Vec3, Colour, f64)> {
+        if self.total_weight <= 0.0 {
+            return None;
+        }
+
+        let (v, j) = sample_cdf(&self.marginal_cdf, sample.0);
+        let row = &self.conditional_cdf[j * (self.width + 1)..(j + 1) * (self.width + 1)];
+        let (u, i) = sample_cdf(row, sample.1);
+
+        let (direction, sin_theta) = lat_long_to_direction(u, v, self.rotation);
+        if sin_theta <= 0.0 {
+            return None;
+        }
+
+        let index = j * self.width + i;
+        let pdf = self.pixel_pdf(index) / (2.0 * consts::PI * consts::PI * sin_theta);
+        Some((direction, self.data[index], pdf))
+    }
+
+    fn pdf(&self, direction: Vec3) -> f64 {
+        if self.total_weight <= 0.0 {
+            return 0.0;
+        }
+
+        let (u, v, sin_theta) = direction_to_lat_long(direction, self.rotation);
+        if sin_theta <= 0.0 {
+            return 0.0;
+        }
+
+        self.pixel_pdf(self.pixel_index(u, v)) / (2.0 * consts::PI * consts::PI * sin_theta)
+    }
+}
+
+/**
+ * Samples a piecewise-constant distribution given by its normalized cumulative distribution
+ * `cdf`.  Returns the sampled position, scaled to [0, 1), and the index of the piece it lies in.
+ */
+#[must_use]
+fn sample_cdf(cdf: &[f64], sample: f64) -> (f64, usize) {
+    let n = cdf.len() - 1;
+    let index = cdf.partition_point(|&c| c <= sample).clamp(1, n) - 1;
+    let width = cdf[index + 1] - cdf[index];
+    let offset = if width > 0.0 {
+        ((sample - cdf[index]) / width).clamp(0.0, 1.0)
+    } else {
+        0.5
+    };
+    (
+        ((index as f64 + offset) / n as f64).min(1.0 - f64::EPSILON),
+        index,
+    )
+}
+
+type ImageData = (usize, usize, Vec<Colour>);
+
+/**
+ * Loads a Radiance HDR image.
+ */
+fn load_hdr(filename: &Path) -> Result<ImageData, Box<dyn Error + Send + Sync>> {
+    let cannot_decode = |x: &dyn Error| {
+        format!(
+            "cannot decode environment map file “{}”: {}",
+            filename.display(),
+            x
+        )
+    };
+
+    let file = match File::open(filename) {
+        Ok(file) => file,
+        Err(x) => {
+            return Err(format!(
+                "cannot open environment map file “{}”: {}",
+                filename.display(),
+                x
+            )
+            .into())
+        }
+    };
+
+    let decoder = HdrDecoder::new(BufReader::new(file)).map_err(|x| cannot_decode(&x))?;
+    let metadata = decoder.metadata();
+    let pixels = decoder.read_image_hdr().map_err(|x| cannot_decode(&x))?;
+
+    let data = pixels
+        .into_iter()
+        .map(|p| Colour(f64::from(p[0]), f64::from(p[1]), f64::from(p[2])))
+        .collect();
+    Ok((metadata.width as usize, metadata.height as usize, data))
+}
+
+/**
+ * Returns `true` if `filename` has the extension of an OpenEXR image.
+ */
+fn is_exr(filename: &Path) -> bool {
+    match filename.extension() {
+        Some(extension) => extension.eq_ignore_ascii_case("exr"),
+        None => false,
+    }
+}
+
+/**
+ * Loads the first RGB layer of an OpenEXR image, at its full resolution.
+ */
+fn load_exr(filename: &Path) -> Result<ImageData, Box<dyn Error + Send + Sync>> {
+    let image = exr::prelude::read_first_rgba_layer_from_file(
+        filename,
+        |resolution, _| {
+            (
+                resolution.width(),
+                vec![Colour(0.0, 0.0, 0.0); resolution.width() * resolution.height()],
+            )
+        },
+        |(width, data), position, (r, g, b, _): (f32, f32, f32, f32)| {
+            data[position.y() * *width + position.x()] =
+                Colour(f64::from(r), f64::from(g), f64::from(b));
+        },
+    )
+    .map_err(|x| {
+        format!(
+            "cannot read environment map file “{}”: {}",
+            filename.display(),
+            x
+        )
+    })?;
+
+    let size = image.layer_data.size;
+    let (_, data) = image.layer_data.channel_data.pixels;
+    Ok((size.width(), size.height(), data))
+}
+
+/**
+ * Loads an 8-bit image.
+ */
+fn load_ldr(filename: &Path) -> Result<ImageData, Box<dyn Error + Send + Sync>> {
+    let data = match Reader::open(filename) {
+        Ok(data) => data,
+        Err(x) => {
+            return Err(format!(
+                "cannot open environment map file “{}”: {}",
+                filename.display(),
+                x
+            )
+            .into())
+        }
+    };
+
+    let data = match data.decode() {
+        Ok(data) => data.to_rgb8(),
+        Err(x) => {
+            return Err(format!(
+                "cannot decode environment map file “{}”: {}",
+                filename.display(),
+                x
+            )
+            .into())
+        }
+    };
+
+    const COLOUR_SCALE: f64 = 1.0 / 255.0;
+    let (width, height) = data.dimensions();
+    let data = data
+        .pixels()
+        .map(|p| COLOUR_SCALE * Colour(f64::from(p[0]), f64::from(p[1]), f64::from(p[2])))
+        .collect();
+    Ok((width as usize, height as usize, data))
+}
@@ -0,0 +1,32 @@
+use super::Background;
+use crate::{Colour, Vec3};
+use std::sync::Arc;
+
+/**
+ * Type for backgrounds blending vertically from one colour straight down to another straight up,
+ * like the sky in “Ray Tracing in One Weekend”.
+ */
+#[derive(Debug)]
+pub struct GradientBackground {
+    bottom: Colour,
+    top: Colour,
+}
+
+impl GradientBackground {
+    #[must_use]
+    pub fn new(bottom: Colour, top: Colour) -> Self {
+        Self { bottom, top }
+    }
+
+    #[must_use]
+    pub fn new_background(bottom: Colour, top: Colour) -> Arc<dyn Background> {
+        Arc::new(Self::new(bottom, top))
+    }
+}
+
+impl Background for GradientBackground {
+    fn value(&self, direction: Vec3) -> Colour {
+        let t = 0.5 * (direction.unit().y() + 1.0);
+        (1.0 - t) * self.bottom + t * self.top
+    }
+}
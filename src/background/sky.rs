@@ -0,0 +1,273 @@
+use super::Background;
+use crate::{Colour, Vec3};
+use std::{f64::consts, sync::Arc};
+
+/**
+ * Luminance of the sun seen from outside the atmosphere, in kcd/m².
+ */
+const SUN_LUMINANCE: f64 = 2.0e6;
+
+/**
+ * Coefficients of the Perez sky luminance distribution for one of the quantities x, y or Y.
+ */
+#[derive(Clone, Copy, Debug)]
+struct Perez([f64; 5]);
+
+impl Perez {
+    /**
+     * Evaluates the distribution for a direction at angle `theta` from the zenith and `gamma` from
+     * the sun.
+     */
+    #[must_use]
+    fn evaluate(&self, cos_theta: f64, gamma: f64) -> f64 {
+        let [a, b, c, d, e] = self.0;
+        let cos_gamma = gamma.cos();
+        (1.0 + a * (b / cos_theta).exp())
+            * (1.0 + c * (d * gamma).exp() + e * cos_gamma * cos_gamma)
+    }
+}
+
+/**
+ * Type for a clear sky lit by the sun, using the analytic model of Preetham, Shirley and Smits
+ * (“A Practical Analytic Model for Daylight”, 1999).
+ *
+ * The sun is a disc of finite angular size, so it casts soft shadows.  It is sampled directly for
+ * lighting, together with the rest of the sky.  Below the horizon, the sky colour at the horizon
+ * is used.
+ */
+#[derive(Debug)]
+pub struct Sky {
+    sun_direction: Vec3,
+    cos_sun_radius: f64,
+    sun_colour: Colour,
+    intensity: f64,
+    perez: [Perez; 3],
+
+    /**
+     * The zenith values of x, y and Y, divided by the Perez distributions at the zenith.
+     */
+    zenith: [f64; 3],
+}
+
+impl Sky {
+    /**
+     * Creates a sky.
+     *
+     * # Parameters
+     *
+     * * `sun_elevation` is the angle of the sun above the horizon, in degrees, from 0 to 90.
+     * * `sun_azimuth` is the direction of the sun about the vertical axis, in degrees, with 0
+     *   being along −z and 90 along +x.
+     * * `turbidity` is the haziness of the atmosphere, from about 2 (very clear) to 10 (hazy).
+     * * `sun_angular_diameter` is the apparent size of the sun, in degrees (about 0.53 for the
+     *   real sun).
+     * * `intensity` scales the brightness of the sky.  Without scaling, luminances are in
+     *   kcd/m², so that a clear sky is around 10 and the sun around 10⁶.
+     */
+    #[must_use]
+    pub fn new(
+        sun_elevation: f64,
+        sun_azimuth: f64,
+        turbidity: f64,
+        sun_angular_diameter: f64,
+        intensity: f64,
+    ) -> Self {
+        assert!((0.0..=90.0).contains(&sun_elevation));
+        assert!((1.0..=20.0).contains(&turbidity));
+        assert!(sun_angular_diameter > 0.0 && sun_angular_diameter < 180.0);
+        assert!(intensity >= 0.0);
+
+        let t = turbidity;
+        let theta_s = (90.0 - sun_elevation).to_radians();
+        let (sin_phi, cos_phi) = sun_azimuth.to_radians().sin_cos();
+        let sun_direction = Vec3(
+            theta_s.sin() * sin_phi,
+            theta_s.cos(),
+            -theta_s.sin() * cos_phi,
+        );
+
+        let perez = [
+            Perez([
+                -0.0193 * t - 0.2592,
+                -0.0665 * t + 0.0008,
+                -0.0004 * t + 0.2125,
+                -0.0641 * t - 0.8989,
+                -0.0033 * t + 0.0452,
+            ]),
+            Perez([
+                -0.0167 * t - 0.2608,
+                -0.0950 * t + 0.0092,
+                -0.0079 * t + 0.2102,
+                -0.0441 * t - 1.6537,
+                -0.0109 * t + 0.0529,
+            ]),
+            Perez([
+                0.1787 * t - 1.4630,
+                -0.3554 * t + 0.4275,
+                -0.0227 * t + 5.3251,
+                0.1206 * t - 2.5771,
+                -0.0670 * t + 0.3703,
+            ]),
+        ];
+
+        // Chromaticity and luminance at the zenith.
+        let zenith_chromaticity = |m: [[f64; 4]; 3]| {
+            let ts = [theta_s.powi(3), theta_s.powi(2), theta_s, 1.0];
+            let tt = [t * t, t, 1.0];
+            (0..3)
+                .map(|i| tt[i] * (0..4).map(|j| m[i][j] * ts[j]).sum::<f64>())
+                .sum::<f64>()
+        };
+        let x_z = zenith_chromaticity([
+            [0.00166, -0.00375, 0.00209, 0.0],
+            [-0.02903, 0.06377, -0.03202, 0.00394],
+            [0.11693, -0.21196, 0.06052, 0.25886],
+        ]);
+        let y_z = zenith_chromaticity([
+            [0.00275, -0.00610, 0.00317, 0.0],
+            [-0.04214, 0.08970, -0.04153, 0.00516],
+            [0.15346, -0.26756, 0.06670, 0.26688],
+        ]);
+        let chi = (4.0 / 9.0 - t / 120.0) * (consts::PI - 2.0 * theta_s);
+        let luminance_z = (4.0453 * t - 4.9710) * chi.tan() - 0.2155 * t + 2.4192;
+
+        let zenith = [x_z, y_z, luminance_z];
+        let mut zenith_scaled = [0.0; 3];
+        for i in 0..3 {
+            zenith_scaled[i] = zenith[i] / perez[i].evaluate(1.0, theta_s);
+        }
+
+        Self {
+            sun_direction,
+            cos_sun_radius: (0.5 * sun_angular_diameter).to_radians().cos(),
+            sun_colour: sun_colour(theta_s, turbidity),
+            intensity,
+            perez,
+            zenith: zenith_scaled,
+        }
+    }
+
+    #[must_use]
+    pub fn new_background(
+        sun_elevation: f64,
+        sun_azimuth: f64,
+        turbidity: f64,
+        sun_angular_diameter: f64,
+        intensity: f64,
+    ) -> Arc<dyn Background> {
+        Arc::new(Self::new(
+            sun_elevation,
+            sun_azimuth,
+            turbidity,
+            sun_angular_diameter,
+            intensity,
+        ))
+    }
+
+    /**
+     * Returns the direction towards the centre of the sun.
+     */
+    #[must_use]
+    pub const fn sun_direction(&self) -> Vec3 {
+        self.sun_direction
+    }
+
+    /**
+     * Returns the colour of the sky, without the sun, looking in the unit vector `direction`.
+     */
+    #[must_use]
+    fn sky_colour(&self, direction: Vec3) -> Colour {
+        let cos_theta = direction.y().max(0.001);
+        let gamma = direction.dot(self.sun_direction).clamp(-1.0, 1.0).acos();
+
+        let [x, y, luminance] =
+            [0, 1, 2].map(|i| self.zenith[i] * self.perez[i].evaluate(cos_theta, gamma));
+
+        // Convert from xyY to XYZ.
+        let colour =
+            Colour::new_from_xyz(x / y * luminance, luminance, (1.0 - x - y) / y * luminance);
+        self.intensity * colour
+    }
+
+    /**
+     * Returns the probability density, per unit solid angle, of sampling a direction within the
+     * sun.
+     */
+    #[must_use]
+    fn sun_pdf(&self) -> f64 {
+        (consts::TAU * (1.0 - self.cos_sun_radius)).recip()
+    }
+}
+
+impl Background for Sky {
+    fn value(&self, direction: Vec3) -> Colour {
+        let direction = direction.unit();
+        let sky = self.sky_colour(direction);
+        if direction.dot(self.sun_direction) >= self.cos_sun_radius {
+            sky + self.intensity * self.sun_colour
+        } else {
+            sky
+        }
+    }
+
+    fn sample(&self, sample: (f64, f64)) -> Option<(Vec3, Colour, f64)> {
+        // Sample the sun half of the time, and the whole sphere otherwise.
+        let (u0, u1) = sample;
+        let direction = if u0 < 0.5 {
+            let cos_theta = 1.0 - 2.0 * u0 * (1.0 - self.cos_sun_radius);
+            let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+            let (sin_phi, cos_phi) = (consts::TAU * u1).sin_cos();
+
+            let w = self.sun_direction;
+            let a = if w.x().abs() > 0.9 {
+                Vec3(0.0, 1.0, 0.0)
+            } else {
+                Vec3(1.0, 0.0, 0.0)
+            };
+            let v = w.cross(a).unit();
+            let u = w.cross(v);
+            sin_theta * cos_phi * u + sin_theta * sin_phi * v + cos_theta * w
+        } else {
+            Vec3::new_unit_from_sample((2.0 * u0 - 1.0, u1))
+        };
+
+        Some((direction, self.value(direction), self.pdf(direction)))
+    }
+
+    fn pdf(&self, direction: Vec3) -> f64 {
+        let sphere_pdf = 0.25 * consts::FRAC_1_PI;
+        if direction.unit().dot(self.sun_direction) >= self.cos_sun_radius {
+            0.5 * (self.sun_pdf() + sphere_pdf)
+        } else {
+            0.5 * sphere_pdf
+        }
+    }
+}
+
+/**
+ * Returns the colour of the sun, attenuated by Rayleigh and aerosol scattering along its path
+ * through the atmosphere, at angle `theta_s` from the zenith.
+ */
+#[must_use]
+fn sun_colour(theta_s: f64, turbidity: f64) -> Colour {
+    // Relative optical air mass (Kasten and Young, 1989).
+    let elevation = 90.0 - theta_s.to_degrees();
+    let air_mass = (theta_s.cos() + 0.50572 * (elevation + 6.07995).powf(-1.6364)).recip();
+
+    // Ångström turbidity coefficient for the given turbidity, with wavelength exponent 1.3.
+    let beta = 0.04608 * turbidity - 0.04586;
+
+    let transmittance = |wavelength: f64| {
+        let rayleigh = 0.008735 * wavelength.powf(-4.08);
+        let aerosol = beta * wavelength.powf(-1.3);
+        (-air_mass * (rayleigh + aerosol)).exp()
+    };
+
+    // Representative wavelengths of the red, green and blue primaries, in micrometres.
+    SUN_LUMINANCE
+        * Colour(
+            transmittance(0.65),
+            transmittance(0.55),
+            transmittance(0.45),
+        )
+}
@@ -0,0 +1,45 @@
+use super::{direction_to_lat_long, Background};
+use crate::{Colour, Texture, Vec3};
+use std::sync::Arc;
+
+/**
+ * Type for backgrounds given by a texture, mapped onto the sphere of directions by latitude and
+ * longitude.
+ *
+ * The texture coordinate `u` runs around the horizon, with 0.5 looking along −z, and `v` runs
+ * from 0 straight down to 1 straight up, so an equirectangular image texture appears the right
+ * way up.  Solid textures are evaluated at the unit vector in each direction.
+ */
+#[derive(Debug)]
+pub struct TextureBackground {
+    texture: Arc<dyn Texture>,
+    rotation: f64,
+}
+
+impl TextureBackground {
+    /**
+     * Creates a texture background, rotated by `rotation` degrees about the vertical axis.
+     */
+    #[must_use]
+    pub fn new(texture: impl Into<Arc<dyn Texture>>, rotation: f64) -> Self {
+        Self {
+            texture: texture.into(),
+            rotation: rotation.to_radians(),
+        }
+    }
+
+    #[must_use]
+    pub fn new_background(
+        texture: impl Into<Arc<dyn Texture>>,
+        rotation: f64,
+    ) -> Arc<dyn Background> {
+        Arc::new(Self::new(texture, rotation))
+    }
+}
+
+impl Background for TextureBackground {
+    fn value(&self, direction: Vec3) -> Colour {
+        let (u, v, _) = direction_to_lat_long(direction, self.rotation);
+        self.texture.value(u, 1.0 - v, direction.unit())
+    }
+}
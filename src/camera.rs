@@ -1,21 +1,28 @@
-use crate::{Ray, Vec3};
-use ::rand::{distributions::Uniform, prelude::*};
-use std::ops::Range;
+mod aperture;
+mod focus;
+mod rig;
+mod stereo;
+mod view;
+use crate::{Hittable, IndependentSampler, Ray, Sampler, Vec3};
+pub use aperture::Aperture;
+pub use focus::FocusTarget;
+pub use rig::{CameraKey, CameraRig, Interpolation};
+use std::{error::Error, f64::consts, ops::Range, sync::Arc};
+pub use stereo::{Stereo, StereoFormat};
+use view::View;
 
 /**
  * Type for representing a viewport.
  */
 #[derive(Clone, Debug)]
 pub struct Camera {
-    time_range: Uniform<f64>,
-    origin: Vec3,
-    horizontal: Vec3,
-    vertical: Vec3,
-    lower_left_corner: Vec3,
-    u: Vec3,
-    v: Vec3,
-    w: Vec3,
+    time_range: Range<f64>,
+    view: View,
+    aspect_ratio: f64,
     lens_radius: f64,
+    aperture: Aperture,
+    stereo: Option<Stereo>,
+    rig: Option<Arc<CameraRig>>,
 }
 
 impl Camera {
@@ -33,44 +40,373 @@ impl Camera {
     ) -> Self {
         assert!(!time_range.is_empty());
 
-        let theta = vfov.to_radians();
-        let h = (0.5 * theta).tan();
-        let viewport_height = 2.0 * h;
-        let viewport_width = aspect_ratio * viewport_height;
-
-        let w = (lookfrom - lookat).unit();
-        let u = vup.cross(w).unit();
-        let v = w.cross(u);
-
-        let time_range = Uniform::from(time_range);
-        let origin = lookfrom;
-        let horizontal = focus_dist * viewport_width * u;
-        let vertical = focus_dist * viewport_height * v;
-        let lower_left_corner = origin - 0.5 * (horizontal + vertical) - focus_dist * w;
+        let view = View::new(lookfrom, lookat, vup, vfov, aspect_ratio, focus_dist);
         let lens_radius = 0.5 * aperture;
 
         Self {
             time_range,
-            origin,
-            horizontal,
-            vertical,
-            lower_left_corner,
-            u,
-            v,
-            w,
+            view,
+            aspect_ratio,
             lens_radius,
+            aperture: Aperture::new_circular(),
+            stereo: None,
+            rig: None,
         }
     }
 
+    /**
+     * Creates a camera modelled on a physical camera with a thin lens.
+     *
+     * # Parameters
+     *
+     * * `lookfrom`, `lookat` and `vup` are as for `Camera::new`.
+     * * `sensor_width` and `sensor_height` are the dimensions of the sensor, in millimetres.  The
+     *   sensor should have the same aspect ratio as the output image.
+     * * `focal_length` is the focal length of the lens, in millimetres.
+     * * `f_number` is the ratio of the focal length to the diameter of the aperture.
+     * * `focus_dist` is the distance to the plane of focus, in scene units.
+     * * `mm_per_unit` is the size of one scene unit, in millimetres.
+     * * `aperture` is the shape of the aperture.
+     * * `time_range` is the interval during which the shutter is open.
+     */
     #[must_use]
-    pub fn get_ray(&self, s: f64, t: f64) -> Ray {
-        let rd = self.lens_radius * Vec3::new_random_in_unit_disk();
-        let offset = self.u * rd.x() + self.v * rd.y();
-
-        Ray::new(
-            self.origin + offset,
-            self.lower_left_corner + s * self.horizontal + t * self.vertical - self.origin - offset,
-            thread_rng().sample(self.time_range),
-        )
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_physical(
+        lookfrom: Vec3,
+        lookat: Vec3,
+        vup: Vec3,
+        sensor_width: f64,
+        sensor_height: f64,
+        focal_length: f64,
+        f_number: f64,
+        focus_dist: f64,
+        mm_per_unit: f64,
+        aperture: Aperture,
+        time_range: Range<f64>,
+    ) -> Self {
+        assert!(sensor_width > 0.0);
+        assert!(sensor_height > 0.0);
+        assert!(focal_length > 0.0);
+        assert!(f_number > 0.0);
+        assert!(mm_per_unit > 0.0);
+
+        // The lens must be further from the sensor than its focal length to focus at a finite
+        // distance, which narrows the field of view (“focus breathing”).
+        let focus_dist_mm = focus_dist * mm_per_unit;
+        assert!(focus_dist_mm > focal_length);
+        let image_dist = focal_length * focus_dist_mm / (focus_dist_mm - focal_length);
+
+        let vfov = 2.0 * (0.5 * sensor_height / image_dist).atan().to_degrees();
+        let aspect_ratio = sensor_width / sensor_height;
+        let aperture_diameter = focal_length / f_number / mm_per_unit;
+
+        let mut cam = Self::new(
+            lookfrom,
+            lookat,
+            vup,
+            vfov,
+            aspect_ratio,
+            aperture_diameter,
+            focus_dist,
+            time_range,
+        );
+        cam.aperture = aperture;
+        cam
+    }
+
+    /**
+     * Creates a stereoscopic camera, which renders the views from both eyes into a single image.
+     *
+     * The parameters are the same as for `Camera::new`, with `aspect_ratio` being the aspect ratio
+     * of the whole output image.  For side-by-side stereo, each eye’s view has half this aspect
+     * ratio.  For omni-directional stereo, each eye sees the full sphere of directions about
+     * `lookfrom`, centred on `lookat`, and `vfov` and `aspect_ratio` are ignored; the output image
+     * should be square.
+     */
+    #[must_use]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_stereo(
+        lookfrom: Vec3,
+        lookat: Vec3,
+        vup: Vec3,
+        vfov: f64,
+        aspect_ratio: f64,
+        aperture: f64,
+        focus_dist: f64,
+        time_range: Range<f64>,
+        stereo: Stereo,
+    ) -> Self {
+        let eye_aspect_ratio = match stereo.format() {
+            StereoFormat::SideBySide => 0.5 * aspect_ratio,
+            StereoFormat::OmniDirectional => aspect_ratio,
+        };
+
+        let mut cam = Self::new(
+            lookfrom,
+            lookat,
+            vup,
+            vfov,
+            eye_aspect_ratio,
+            aperture,
+            focus_dist,
+            time_range,
+        );
+        cam.stereo = Some(stereo);
+        cam
+    }
+
+    /**
+     * Animates the camera.  The camera’s position, orientation, field of view and focus distance
+     * are taken from `rig` at the time of each ray, so any movement during the shutter interval
+     * blurs the image.  The lens and the aspect ratio are unchanged, and the rig’s focus distances
+     * replace any set by `focus_on`.
+     */
+    pub fn set_rig(&mut self, rig: Arc<CameraRig>) {
+        self.rig = Some(rig);
+    }
+
+    /**
+     * Changes the time interval during which the shutter is open.
+     */
+    pub fn set_time_range(&mut self, time_range: Range<f64>) {
+        assert!(!time_range.is_empty());
+        self.time_range = time_range;
+    }
+
+    /**
+     * Changes the focus distance of the camera, keeping the field of view unchanged.
+     *
+     * For `FocusTarget::Point`, the focus distance is the depth of the point along the viewing
+     * direction.  For `FocusTarget::Pixel`, a probe ray is traced through the centre of the lens
+     * into `world`, and the camera focuses on the nearest object it hits.
+     *
+     * # Errors
+     *
+     * Returns an error if the target is not in front of the camera, if the probe ray is blocked by
+     * vignetting or hits nothing, or if the camera has a rig, whose keyframes set the focus
+     * distance instead.
+     */
+    pub fn focus_on(
+        &mut self,
+        target: FocusTarget,
+        world: &dyn Hittable,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        if self.rig.is_some() {
+            return Err(
+                "cannot focus a camera with a rig; set the focus distance of its keys".into(),
+            );
+        }
+
+        let focus_dist = match target {
+            FocusTarget::Distance(focus_dist) => focus_dist,
+
+            FocusTarget::Point(p) => self.view.depth(p),
+
+            FocusTarget::Pixel(s, t) => {
+                let mut pinhole = self.clone();
+                pinhole.lens_radius = 0.0;
+                pinhole.aperture = Aperture::new_circular();
+
+                let probe = match pinhole.get_ray(s, t, &mut IndependentSampler::new()) {
+                    Some(probe) => probe,
+                    None => return Err("focus probe is blocked by vignetting".into()),
+                };
+                match world.hit(&probe, 0.001..f64::INFINITY) {
+                    Some(rec) => self.view.depth(rec.p()),
+                    None => return Err("focus probe hit nothing".into()),
+                }
+            }
+        };
+
+        if focus_dist.is_nan() || focus_dist <= 0.0 {
+            return Err("focus target is not in front of the camera".into());
+        }
+        self.view.set_focus_dist(focus_dist);
+        Ok(())
+    }
+
+    /**
+     * Returns the ranges of image coordinates `s` and `t` covered by the view containing the point
+     * `(s, t)`: the whole image, or for a stereo camera the part of it showing one eye’s view.
+     */
+    #[must_use]
+    pub(crate) fn view_bounds(&self, s: f64, t: f64) -> (Range<f64>, Range<f64>) {
+        match self.stereo {
+            None => (0.0..1.0, 0.0..1.0),
+            Some(stereo) => stereo.bounds(s, t),
+        }
+    }
+
+    /**
+     * Generates a ray through the point `(s, t)` on the image, where both coordinates are in the
+     * range [0, 1].  The point on the lens and the time are taken from `sampler`.  Returns
+     * `None` if the ray is blocked by vignetting.
+     */
+    #[must_use]
+    pub fn get_ray(&self, s: f64, t: f64, sampler: &mut dyn Sampler) -> Option<Ray> {
+        let lens_sample = sampler.get_2d();
+        let shutter = sampler.get_1d();
+        let time = self.time_range.start + shutter * (self.time_range.end - self.time_range.start);
+
+        match &self.rig {
+            None => self.get_view_ray(&self.view, s, t, time, lens_sample),
+
+            Some(rig) => {
+                let key = rig.key_at(time);
+                let view = View::new(
+                    key.lookfrom(),
+                    key.lookat(),
+                    key.vup(),
+                    key.vfov(),
+                    self.aspect_ratio,
+                    key.focus_dist(),
+                );
+                self.get_view_ray(&view, s, t, time, lens_sample)
+            }
+        }
+    }
+
+    #[must_use]
+    fn get_view_ray(
+        &self,
+        view: &View,
+        s: f64,
+        t: f64,
+        time: f64,
+        lens_sample: (f64, f64),
+    ) -> Option<Ray> {
+        match self.stereo {
+            None => {
+                let focus = view.lower_left_corner + s * view.horizontal + t * view.vertical;
+                let image_pos = view.image_pos(s, t);
+                self.lens_ray(
+                    view.origin,
+                    focus,
+                    view.u,
+                    view.v,
+                    image_pos,
+                    time,
+                    lens_sample,
+                )
+            }
+
+            Some(stereo) => {
+                let (offset, s, t) = stereo.split(s, t);
+                let convergence_dist = stereo.convergence_distance();
+                match stereo.format() {
+                    StereoFormat::SideBySide => self.side_by_side_ray(
+                        view,
+                        offset,
+                        convergence_dist,
+                        s,
+                        t,
+                        time,
+                        lens_sample,
+                    ),
+                    StereoFormat::OmniDirectional => self.omni_directional_ray(
+                        view,
+                        offset,
+                        convergence_dist,
+                        s,
+                        t,
+                        time,
+                        lens_sample,
+                    ),
+                }
+            }
+        }
+    }
+
+    /**
+     * Generates a ray for one eye of a side-by-side stereo pair.  The eye is displaced by
+     * `offset` along the camera’s horizontal axis, and its frustum is sheared (rather than
+     * rotated) so that both eyes’ views coincide at the convergence distance.
+     */
+    #[must_use]
+    #[allow(clippy::too_many_arguments)]
+    fn side_by_side_ray(
+        &self,
+        view: &View,
+        offset: f64,
+        convergence_dist: f64,
+        s: f64,
+        t: f64,
+        time: f64,
+        lens_sample: (f64, f64),
+    ) -> Option<Ray> {
+        // Direction of the centre line of sight, scaled to unit depth.
+        let target = view.lower_left_corner + s * view.horizontal + t * view.vertical;
+        let direction = (target - view.origin) / view.focus_dist;
+
+        let eye = view.origin + offset * view.u;
+        let eye_direction = if convergence_dist.is_finite() {
+            (view.origin + convergence_dist * direction - eye) / convergence_dist
+        } else {
+            direction
+        };
+
+        let focus = eye + view.focus_dist * eye_direction;
+        let image_pos = view.image_pos(s, t);
+        self.lens_ray(eye, focus, view.u, view.v, image_pos, time, lens_sample)
+    }
+
+    /**
+     * Generates a ray for one eye of an omni-directional stereo panorama.  The eye is displaced
+     * by `offset` perpendicular to the line of sight, on a circle about the camera position.
+     */
+    #[must_use]
+    #[allow(clippy::too_many_arguments)]
+    fn omni_directional_ray(
+        &self,
+        view: &View,
+        offset: f64,
+        convergence_dist: f64,
+        s: f64,
+        t: f64,
+        time: f64,
+        lens_sample: (f64, f64),
+    ) -> Option<Ray> {
+        let theta = (s - 0.5) * consts::TAU; // Longitude.
+        let phi = (t - 0.5) * consts::PI; // Latitude.
+        let (sin_theta, cos_theta) = theta.sin_cos();
+        let (sin_phi, cos_phi) = phi.sin_cos();
+
+        let direction =
+            sin_theta * cos_phi * view.u + sin_phi * view.v - cos_theta * cos_phi * view.w;
+        let right = cos_theta * view.u + sin_theta * view.w;
+
+        let eye = view.origin + offset * right;
+        let eye_direction = if convergence_dist.is_finite() {
+            (view.origin + convergence_dist * direction - eye).unit()
+        } else {
+            direction
+        };
+
+        let focus = eye + view.focus_dist * eye_direction;
+        // A panorama has no image edges, so there is no vignetting.
+        let lens_v = right.cross(eye_direction);
+        self.lens_ray(eye, focus, right, lens_v, (0.0, 0.0), time, lens_sample)
+    }
+
+    /**
+     * Generates a ray from the point on the lens centred on `eye`, with axes `lens_u` and `lens_v`,
+     * selected by `lens_sample`, through the point `focus` on the plane of focus.
+     */
+    #[must_use]
+    #[allow(clippy::too_many_arguments)]
+    fn lens_ray(
+        &self,
+        eye: Vec3,
+        focus: Vec3,
+        lens_u: Vec3,
+        lens_v: Vec3,
+        image_pos: (f64, f64),
+        time: f64,
+        lens_sample: (f64, f64),
+    ) -> Option<Ray> {
+        let (x, y) = self.aperture.sample(image_pos, lens_sample)?;
+        let offset = self.lens_radius * (lens_u * x + lens_v * y);
+
+        Some(Ray::new(eye + offset, focus - eye - offset, time))
     }
 }
@@ -0,0 +1,95 @@
+use crate::Vec3;
+use std::f64::consts;
+
+/**
+ * Type for representing the shape of a lens aperture.
+ */
+#[derive(Clone, Copy, Debug)]
+pub struct Aperture {
+    blades: u32,
+    rotation: f64,
+    cats_eye: f64,
+}
+
+impl Aperture {
+    /**
+     * Creates a perfectly circular aperture, with no vignetting.
+     */
+    #[must_use]
+    pub const fn new_circular() -> Self {
+        Self {
+            blades: 0,
+            rotation: 0.0,
+            cats_eye: 0.0,
+        }
+    }
+
+    /**
+     * Creates an aperture shape.
+     *
+     * # Parameters
+     *
+     * * `blades` is the number of diaphragm blades.  The aperture is a regular polygon with this
+     *   many sides, or a circle if there are fewer than three blades.
+     * * `rotation` is the rotation of the polygon, in degrees.
+     * * `cats_eye` is the strength of optical vignetting, which clips the aperture towards the
+     *   edges of the image, producing “cat’s-eye” bokeh and darkening the corners.  Zero means no
+     *   vignetting; one means the aperture is clipped to nothing in the image corners.
+     */
+    #[must_use]
+    pub fn new(blades: u32, rotation: f64, cats_eye: f64) -> Self {
+        assert!(cats_eye >= 0.0);
+        Self {
+            blades,
+            rotation: rotation.to_radians(),
+            cats_eye,
+        }
+    }
+
+    /**
+     * Maps the uniform sample `sample` to a point on the aperture, scaled to unit circumradius.
+     *
+     * `image_pos` is the position on the image, scaled so that the image corners are at unit
+     * distance from the centre.  Returns `None` if the sampled point is blocked by vignetting.
+     */
+    #[must_use]
+    pub(super) fn sample(&self, image_pos: (f64, f64), sample: (f64, f64)) -> Option<(f64, f64)> {
+        let (x, y) = if self.blades < 3 {
+            let p = Vec3::new_in_unit_disk_from_sample(sample);
+            (p.x(), p.y())
+        } else {
+            // Pick one of the triangles making up the polygon, reusing the remainder of the first
+            // coordinate to pick a point within it.
+            let n = f64::from(self.blades);
+            let (u0, u1) = sample;
+            let k = (u0 * n).floor().min(n - 1.0);
+            let u0 = u0 * n - k;
+            let angle0 = self.rotation + k * consts::TAU / n;
+            let angle1 = self.rotation + (k + 1.0) * consts::TAU / n;
+            let (sin0, cos0) = angle0.sin_cos();
+            let (sin1, cos1) = angle1.sin_cos();
+
+            let r = u0.sqrt();
+            let a = r * (1.0 - u1);
+            let b = r * u1;
+
+            (a * cos0 + b * cos1, a * sin0 + b * sin1)
+        };
+
+        // Optical vignetting: the aperture is seen through a second, off-centre stop.
+        let (ix, iy) = image_pos;
+        let dx = x - 2.0 * self.cats_eye * ix;
+        let dy = y - 2.0 * self.cats_eye * iy;
+        if dx * dx + dy * dy > 1.0 {
+            None
+        } else {
+            Some((x, y))
+        }
+    }
+}
+
+impl Default for Aperture {
+    fn default() -> Self {
+        Self::new_circular()
+    }
+}
@@ -0,0 +1,23 @@
+use crate::Vec3;
+
+/**
+ * Type for specifying what a camera should focus on.
+ */
+#[derive(Clone, Copy, Debug)]
+pub enum FocusTarget {
+    /**
+     * Focus at a fixed distance from the camera, in scene units.
+     */
+    Distance(f64),
+
+    /**
+     * Focus on a point in world space.
+     */
+    Point(Vec3),
+
+    /**
+     * Focus on whatever is visible at the image coordinates `(s, t)`, where both coordinates are
+     * in the range [0, 1].
+     */
+    Pixel(f64, f64),
+}
@@ -0,0 +1,197 @@
+use crate::Vec3;
+
+/**
+ * Type for representing the camera parameters at one instant of an animation.
+ */
+#[derive(Clone, Copy, Debug)]
+pub struct CameraKey {
+    time: f64,
+    lookfrom: Vec3,
+    lookat: Vec3,
+    vup: Vec3,
+    vfov: f64,
+    focus_dist: f64,
+}
+
+impl CameraKey {
+    /**
+     * Creates a keyframe.  The parameters other than `time` are as for `Camera::new`.
+     */
+    #[must_use]
+    pub fn new(
+        time: f64,
+        lookfrom: Vec3,
+        lookat: Vec3,
+        vup: Vec3,
+        vfov: f64,
+        focus_dist: f64,
+    ) -> Self {
+        Self {
+            time,
+            lookfrom,
+            lookat,
+            vup,
+            vfov,
+            focus_dist,
+        }
+    }
+
+    #[must_use]
+    pub const fn time(&self) -> f64 {
+        self.time
+    }
+
+    #[must_use]
+    pub const fn lookfrom(&self) -> Vec3 {
+        self.lookfrom
+    }
+
+    #[must_use]
+    pub const fn lookat(&self) -> Vec3 {
+        self.lookat
+    }
+
+    #[must_use]
+    pub const fn vup(&self) -> Vec3 {
+        self.vup
+    }
+
+    #[must_use]
+    pub const fn vfov(&self) -> f64 {
+        self.vfov
+    }
+
+    #[must_use]
+    pub const fn focus_dist(&self) -> f64 {
+        self.focus_dist
+    }
+}
+
+/**
+ * Methods of interpolating between keyframes.
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Interpolation {
+    /**
+     * Straight lines between keyframes.
+     */
+    Linear,
+
+    /**
+     * A Catmull–Rom spline through the keyframes, which is smooth at each keyframe.
+     */
+    CatmullRom,
+}
+
+/**
+ * Type for a camera whose parameters are keyframed over time.
+ */
+#[derive(Clone, Debug)]
+pub struct CameraRig {
+    keys: Vec<CameraKey>,
+    interpolation: Interpolation,
+}
+
+impl CameraRig {
+    /**
+     * Creates a camera rig from a set of keyframes, in any order.  Before the first and after the
+     * last keyframe, the camera is held still.
+     */
+    #[must_use]
+    pub fn new(mut keys: Vec<CameraKey>, interpolation: Interpolation) -> Self {
+        assert!(!keys.is_empty());
+        keys.sort_by(|a, b| {
+            a.time
+                .partial_cmp(&b.time)
+                .expect("unexpected NaN in keyframe time")
+        });
+        Self {
+            keys,
+            interpolation,
+        }
+    }
+
+    /**
+     * Returns the camera parameters at time `time`.
+     */
+    #[must_use]
+    pub fn key_at(&self, time: f64) -> CameraKey {
+        let n = self.keys.len();
+
+        // Index of the first keyframe after `time`.
+        let i = self.keys.partition_point(|key| key.time <= time);
+        if i == 0 {
+            return CameraKey {
+                time,
+                ..self.keys[0]
+            };
+        }
+        if i == n {
+            return CameraKey {
+                time,
+                ..self.keys[n - 1]
+            };
+        }
+
+        let k0 = &self.keys[(i - 1).saturating_sub(1)];
+        let k1 = &self.keys[i - 1];
+        let k2 = &self.keys[i];
+        let k3 = &self.keys[(i + 1).min(n - 1)];
+
+        let interpolate = |f: fn(&CameraKey) -> Vec3| match self.interpolation {
+            Interpolation::Linear => lerp(f(k1), f(k2), k1.time, k2.time, time),
+            Interpolation::CatmullRom => catmull_rom(
+                [f(k0), f(k1), f(k2), f(k3)],
+                [k0.time, k1.time, k2.time, k3.time],
+                time,
+            ),
+        };
+
+        let scalars = interpolate(|k| Vec3(k.vfov, k.focus_dist, 0.0));
+
+        CameraKey {
+            time,
+            lookfrom: interpolate(|k| k.lookfrom),
+            lookat: interpolate(|k| k.lookat),
+            vup: interpolate(|k| k.vup).unit(),
+            vfov: scalars.x(),
+            focus_dist: scalars.y(),
+        }
+    }
+}
+
+#[must_use]
+fn lerp(p1: Vec3, p2: Vec3, t1: f64, t2: f64, t: f64) -> Vec3 {
+    let a = (t - t1) / (t2 - t1);
+    p1 + (p2 - p1) * a
+}
+
+/**
+ * Evaluates a Catmull–Rom spline between `p[1]` and `p[2]`, with tangents scaled for keyframes
+ * that are not evenly spaced in time.
+ */
+#[must_use]
+fn catmull_rom(p: [Vec3; 4], t: [f64; 4], time: f64) -> Vec3 {
+    let dt = t[2] - t[1];
+
+    let tangent = |i: usize, j: usize| {
+        if t[j] > t[i] {
+            (p[j] - p[i]) * (dt / (t[j] - t[i]))
+        } else {
+            p[j] - p[i]
+        }
+    };
+    let m1 = tangent(0, 2);
+    let m2 = tangent(1, 3);
+
+    // Cubic Hermite basis.
+    let a = (time - t[1]) / dt;
+    let a2 = a * a;
+    let a3 = a2 * a;
+    let h00 = 2.0 * a3 - 3.0 * a2 + 1.0;
+    let h10 = a3 - 2.0 * a2 + a;
+    let h01 = -2.0 * a3 + 3.0 * a2;
+    let h11 = a3 - a2;
+
+    p[1] * h00 + m1 * h10 + p[2] * h01 + m2 * h11
+}
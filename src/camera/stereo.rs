@@ -0,0 +1,124 @@
+use std::ops::Range;
+
+/**
+ * Layout of the two eye views within a stereoscopic image.
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StereoFormat {
+    /**
+     * Perspective views, with the left eye in the left half of the image and the right eye in the
+     * right half.
+     */
+    SideBySide,
+
+    /**
+     * Omni-directional stereo (ODS), with an equirectangular panorama for the left eye in the top
+     * half of the image and one for the right eye in the bottom half.
+     */
+    OmniDirectional,
+}
+
+/**
+ * Type for representing the parameters of a stereoscopic camera.
+ */
+#[derive(Clone, Copy, Debug)]
+pub struct Stereo {
+    format: StereoFormat,
+    interpupillary_distance: f64,
+    convergence_distance: f64,
+}
+
+impl Stereo {
+    /**
+     * Creates stereo parameters.
+     *
+     * # Parameters
+     *
+     * * `format` is the layout of the two eye views in the output image.
+     * * `interpupillary_distance` is the distance between the eyes, in scene units.
+     * * `convergence_distance` is the distance at which the lines of sight of the two eyes meet.
+     *   Objects at this distance appear at the depth of the screen.  Use `f64::INFINITY` for
+     *   parallel lines of sight.
+     */
+    #[must_use]
+    pub fn new(
+        format: StereoFormat,
+        interpupillary_distance: f64,
+        convergence_distance: f64,
+    ) -> Self {
+        assert!(interpupillary_distance >= 0.0);
+        assert!(convergence_distance > 0.0);
+        Self {
+            format,
+            interpupillary_distance,
+            convergence_distance,
+        }
+    }
+
+    #[must_use]
+    pub const fn format(&self) -> StereoFormat {
+        self.format
+    }
+
+    #[must_use]
+    pub const fn interpupillary_distance(&self) -> f64 {
+        self.interpupillary_distance
+    }
+
+    #[must_use]
+    pub const fn convergence_distance(&self) -> f64 {
+        self.convergence_distance
+    }
+
+    /**
+     * Returns the ranges of image coordinates `s` and `t` covered by the view of the eye that sees
+     * the point `(s, t)`.
+     */
+    #[must_use]
+    pub(super) fn bounds(&self, s: f64, t: f64) -> (Range<f64>, Range<f64>) {
+        match self.format {
+            StereoFormat::SideBySide => {
+                if s < 0.5 {
+                    (0.0..0.5, 0.0..1.0)
+                } else {
+                    (0.5..1.0, 0.0..1.0)
+                }
+            }
+
+            StereoFormat::OmniDirectional => {
+                if t < 0.5 {
+                    (0.0..1.0, 0.0..0.5)
+                } else {
+                    (0.0..1.0, 0.5..1.0)
+                }
+            }
+        }
+    }
+
+    /**
+     * Splits image coordinates `(s, t)` into the signed eye offset (negative for the left eye,
+     * positive for the right eye) and the coordinates within that eye’s view.
+     */
+    #[must_use]
+    pub(super) fn split(&self, s: f64, t: f64) -> (f64, f64, f64) {
+        let half_ipd = 0.5 * self.interpupillary_distance;
+        match self.format {
+            StereoFormat::SideBySide => {
+                if s < 0.5 {
+                    (-half_ipd, 2.0 * s, t)
+                } else {
+                    (half_ipd, 2.0 * s - 1.0, t)
+                }
+            }
+
+            StereoFormat::OmniDirectional => {
+                // Image row coordinates increase upwards, so the top half has `t` ≥ 0.5.
+                if t < 0.5 {
+                    (half_ipd, s, 2.0 * t)
+                } else {
+                    (-half_ipd, s, 2.0 * t - 1.0)
+                }
+            }
+        }
+    }
+}
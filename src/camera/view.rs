@@ -0,0 +1,85 @@
+use crate::Vec3;
+
+/**
+ * Type for representing the position and orientation of a camera, and its plane of focus.
+ */
+#[derive(Clone, Debug)]
+pub(super) struct View {
+    pub(super) origin: Vec3,
+    pub(super) horizontal: Vec3,
+    pub(super) vertical: Vec3,
+    pub(super) lower_left_corner: Vec3,
+    pub(super) u: Vec3,
+    pub(super) v: Vec3,
+    pub(super) w: Vec3,
+    pub(super) focus_dist: f64,
+    viewport_width: f64,
+    viewport_height: f64,
+}
+
+impl View {
+    #[must_use]
+    pub(super) fn new(
+        lookfrom: Vec3,
+        lookat: Vec3,
+        vup: Vec3,
+        vfov: f64,
+        aspect_ratio: f64,
+        focus_dist: f64,
+    ) -> Self {
+        let theta = vfov.to_radians();
+        let h = (0.5 * theta).tan();
+        let viewport_height = 2.0 * h;
+        let viewport_width = aspect_ratio * viewport_height;
+
+        let w = (lookfrom - lookat).unit();
+        let u = vup.cross(w).unit();
+        let v = w.cross(u);
+
+        let origin = lookfrom;
+        let horizontal = focus_dist * viewport_width * u;
+        let vertical = focus_dist * viewport_height * v;
+        let lower_left_corner = origin - 0.5 * (horizontal + vertical) - focus_dist * w;
+
+        Self {
+            origin,
+            horizontal,
+            vertical,
+            lower_left_corner,
+            u,
+            v,
+            w,
+            focus_dist,
+            viewport_width,
+            viewport_height,
+        }
+    }
+
+    pub(super) fn set_focus_dist(&mut self, focus_dist: f64) {
+        self.horizontal = focus_dist * self.viewport_width * self.u;
+        self.vertical = focus_dist * self.viewport_height * self.v;
+        self.lower_left_corner =
+            self.origin - 0.5 * (self.horizontal + self.vertical) - focus_dist * self.w;
+        self.focus_dist = focus_dist;
+    }
+
+    /**
+     * Returns the depth of the point `p` along the viewing direction.
+     */
+    #[must_use]
+    pub(super) fn depth(&self, p: Vec3) -> f64 {
+        (self.origin - p).dot(self.w)
+    }
+
+    /**
+     * Converts image coordinates to a position relative to the image centre, scaled so that the
+     * corners are at unit distance.
+     */
+    #[must_use]
+    pub(super) fn image_pos(&self, s: f64, t: f64) -> (f64, f64) {
+        let diagonal = self.viewport_width.hypot(self.viewport_height);
+        let x = (2.0 * s - 1.0) * self.viewport_width / diagonal;
+        let y = (2.0 * t - 1.0) * self.viewport_height / diagonal;
+        (x, y)
+    }
+}
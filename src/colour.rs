@@ -1,5 +1,9 @@
+use crate::spectrum;
 use rand::{distributions::Uniform, prelude::*};
-use std::ops::{self, Range};
+use std::{
+    ops::{self, Range},
+    sync::OnceLock,
+};
 
 /**
  * Type for representing colours.
@@ -23,6 +27,73 @@ impl Colour {
         Colour(r, g, b)
     }
 
+    /**
+     * Creates a colour from CIE 1931 XYZ tristimulus values, converted to linear sRGB.
+     */
+    #[must_use]
+    #[allow(clippy::many_single_char_names)]
+    pub fn new_from_xyz(x: f64, y: f64, z: f64) -> Self {
+        let r = 3.2406 * x - 1.5372 * y - 0.4986 * z;
+        let g = -0.9689 * x + 1.8758 * y + 0.0415 * z;
+        let b = 0.0557 * x - 0.2040 * y + 1.0570 * z;
+        Colour(r, g, b)
+    }
+
+    /**
+     * Creates the colour of the light emitted by a black body at `temperature`, in kelvins.
+     *
+     * If `normalise` is `true`, the colour is scaled to unit luminance, so that only its hue
+     * depends on the temperature.  Otherwise, it is the radiance in W/(sr·m²), weighted by the
+     * colour matching functions, which rises steeply with temperature.
+     */
+    #[must_use]
+    pub fn new_blackbody(temperature: f64, normalise: bool) -> Self {
+        assert!(temperature > 0.0);
+
+        let (mut x, mut y, mut z) = (0.0, 0.0, 0.0);
+        let mut wavelength = spectrum::WAVELENGTH_MIN;
+        while wavelength <= spectrum::WAVELENGTH_MAX {
+            let radiance = spectrum::planck(wavelength, temperature);
+            let (cie_x, cie_y, cie_z) = spectrum::cie_xyz(wavelength);
+            x += radiance * cie_x;
+            y += radiance * cie_y;
+            z += radiance * cie_z;
+            wavelength += 1.0;
+        }
+
+        let colour = Self::new_from_xyz(x, y, z);
+        if normalise && y > 0.0 {
+            colour / y
+        } else {
+            colour
+        }
+    }
+
+    /**
+     * Creates the colour seen in light of unit spectral radiance at `wavelength`, in nanometres,
+     * scaled so that the colours of all the wavelengths together, as for light of equal radiance
+     * at every wavelength, sum to white.  Some components are negative for wavelengths outside
+     * the sRGB gamut.
+     */
+    #[must_use]
+    pub(crate) fn new_monochromatic(wavelength: f64) -> Self {
+        static WHITE: OnceLock<Colour> = OnceLock::new();
+        let white = WHITE.get_or_init(|| {
+            let mut white = Colour(0.0, 0.0, 0.0);
+            let mut wavelength = spectrum::WAVELENGTH_MIN;
+            while wavelength <= spectrum::WAVELENGTH_MAX {
+                let (x, y, z) = spectrum::cie_xyz(wavelength);
+                white += Self::new_from_xyz(x, y, z);
+                wavelength += 1.0;
+            }
+            white
+        });
+
+        let (x, y, z) = spectrum::cie_xyz(wavelength);
+        let Colour(r, g, b) = Self::new_from_xyz(x, y, z);
+        Colour(r / white.0, g / white.1, b / white.2)
+    }
+
     #[must_use]
     pub const fn r(self) -> f64 {
         self.0
@@ -38,6 +109,60 @@ impl Colour {
         self.2
     }
 
+    /**
+     * Returns the CIE 1931 XYZ tristimulus values of the colour, taken as linear sRGB.
+     */
+    #[must_use]
+    pub fn to_xyz(self) -> (f64, f64, f64) {
+        let Colour(r, g, b) = self;
+        let x = 0.4124 * r + 0.3576 * g + 0.1805 * b;
+        let y = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+        let z = 0.0193 * r + 0.1192 * g + 0.9505 * b;
+        (x, y, z)
+    }
+
+    /**
+     * Adjusts the colour from a scene lit by light of colour `white` to how it would appear under
+     * the sRGB white point (D65), by scaling the Bradford cone responses (a von Kries
+     * transform).  Surfaces lit by `white` then appear neutral.
+     */
+    #[must_use]
+    pub fn white_balance(self, white: Self) -> Self {
+        const BRADFORD: [[f64; 3]; 3] = [
+            [0.8951, 0.2664, -0.1614],
+            [-0.7502, 1.7135, 0.0367],
+            [0.0389, -0.0685, 1.0296],
+        ];
+        const BRADFORD_INVERSE: [[f64; 3]; 3] = [
+            [0.986_992_9, -0.147_054_3, 0.159_962_7],
+            [0.432_305_3, 0.518_360_3, 0.049_291_2],
+            [-0.008_528_7, 0.040_042_8, 0.968_486_7],
+        ];
+        const D65: [f64; 3] = [0.950_47, 1.0, 1.088_83];
+
+        let transform = |m: &[[f64; 3]; 3], v: [f64; 3]| {
+            [0, 1, 2].map(|i| m[i][0] * v[0] + m[i][1] * v[1] + m[i][2] * v[2])
+        };
+
+        let (x, y, z) = white.to_xyz();
+        let source = transform(&BRADFORD, [x / y, 1.0, z / y]);
+        let destination = transform(&BRADFORD, D65);
+
+        let (x, y, z) = self.to_xyz();
+        let cone = transform(&BRADFORD, [x, y, z]);
+        let cone = [0, 1, 2].map(|i| cone[i] * destination[i] / source[i]);
+        let [x, y, z] = transform(&BRADFORD_INVERSE, cone);
+        Self::new_from_xyz(x, y, z)
+    }
+
+    /**
+     * Returns the relative luminance of the colour, for Rec. 709 primaries.
+     */
+    #[must_use]
+    pub fn luminance(self) -> f64 {
+        0.2126 * self.0 + 0.7152 * self.1 + 0.0722 * self.2
+    }
+
     #[must_use]
     pub fn to_rgb8(self, samples_per_pixel: u32) -> (u8, u8, u8) {
         assert!(samples_per_pixel > 0);
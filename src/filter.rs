@@ -0,0 +1,28 @@
+mod boxfilter;
+mod gaussianfilter;
+mod lanczosfilter;
+mod mitchellfilter;
+mod tentfilter;
+pub use boxfilter::BoxFilter;
+pub use gaussianfilter::GaussianFilter;
+pub use lanczosfilter::LanczosFilter;
+pub use mitchellfilter::MitchellFilter;
+use std::fmt::Debug;
+pub use tentfilter::TentFilter;
+
+/**
+ * Trait for pixel reconstruction filters.
+ *
+ * Each sample contributes to every pixel whose centre is within the filter’s radius, weighted by
+ * the filter evaluated at the offset from the pixel centre to the sample, in pixels.
+ */
+pub trait Filter: Debug + Send + Sync {
+    /**
+     * Returns the radius of the filter’s support, in pixels.
+     */
+    #[must_use]
+    fn radius(&self) -> f64;
+
+    #[must_use]
+    fn evaluate(&self, x: f64, y: f64) -> f64;
+}
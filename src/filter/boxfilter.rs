@@ -0,0 +1,39 @@
+use super::Filter;
+use std::sync::Arc;
+
+/**
+ * Type for a box filter, which weights all samples within its radius equally.
+ */
+#[derive(Debug)]
+pub struct BoxFilter {
+    radius: f64,
+}
+
+impl BoxFilter {
+    #[must_use]
+    pub fn new(radius: f64) -> Self {
+        assert!(radius > 0.0);
+        Self { radius }
+    }
+
+    #[must_use]
+    pub fn new_filter(radius: f64) -> Arc<dyn Filter> {
+        Arc::new(Self::new(radius))
+    }
+}
+
+impl Filter for BoxFilter {
+    fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    fn evaluate(&self, x: f64, y: f64) -> f64 {
+        // Half-open, so that with a radius of half a pixel each sample lands in exactly one pixel.
+        let r = self.radius;
+        if (-r..r).contains(&x) && (-r..r).contains(&y) {
+            1.0
+        } else {
+            0.0
+        }
+    }
+}
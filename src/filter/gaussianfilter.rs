@@ -0,0 +1,51 @@
+use super::Filter;
+use std::sync::Arc;
+
+/**
+ * Type for a Gaussian filter, truncated at its radius.
+ */
+#[derive(Debug)]
+pub struct GaussianFilter {
+    radius: f64,
+    alpha: f64,
+    edge: f64,
+}
+
+impl GaussianFilter {
+    /**
+     * Creates a Gaussian filter with standard deviation `sigma` pixels.  The Gaussian is shifted
+     * down so that it falls to zero at `radius`.
+     */
+    #[must_use]
+    pub fn new(radius: f64, sigma: f64) -> Self {
+        assert!(radius > 0.0);
+        assert!(sigma > 0.0);
+        let alpha = 0.5 / (sigma * sigma);
+        let edge = (-alpha * radius * radius).exp();
+        Self {
+            radius,
+            alpha,
+            edge,
+        }
+    }
+
+    #[must_use]
+    pub fn new_filter(radius: f64, sigma: f64) -> Arc<dyn Filter> {
+        Arc::new(Self::new(radius, sigma))
+    }
+
+    #[must_use]
+    fn gaussian(&self, x: f64) -> f64 {
+        ((-self.alpha * x * x).exp() - self.edge).max(0.0)
+    }
+}
+
+impl Filter for GaussianFilter {
+    fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    fn evaluate(&self, x: f64, y: f64) -> f64 {
+        self.gaussian(x) * self.gaussian(y)
+    }
+}
@@ -0,0 +1,58 @@
+use super::Filter;
+use std::{f64::consts, sync::Arc};
+
+/**
+ * Type for a Lanczos filter: a sinc function windowed by a wider sinc function.
+ */
+#[derive(Debug)]
+pub struct LanczosFilter {
+    radius: f64,
+}
+
+impl LanczosFilter {
+    /**
+     * Creates a Lanczos filter.  The radius is also the number of lobes on each side.
+     */
+    #[must_use]
+    pub fn new(radius: f64) -> Self {
+        assert!(radius > 0.0);
+        Self { radius }
+    }
+
+    #[must_use]
+    pub fn new_filter(radius: f64) -> Arc<dyn Filter> {
+        Arc::new(Self::new(radius))
+    }
+
+    #[must_use]
+    fn lanczos(&self, x: f64) -> f64 {
+        if x.abs() >= self.radius {
+            0.0
+        } else {
+            sinc(x) * sinc(x / self.radius)
+        }
+    }
+}
+
+impl Filter for LanczosFilter {
+    fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    fn evaluate(&self, x: f64, y: f64) -> f64 {
+        self.lanczos(x) * self.lanczos(y)
+    }
+}
+
+/**
+ * Normalized sinc function.
+ */
+#[must_use]
+fn sinc(x: f64) -> f64 {
+    let x = consts::PI * x;
+    if x.abs() < 1e-5 {
+        1.0 - x * x / 6.0
+    } else {
+        x.sin() / x
+    }
+}
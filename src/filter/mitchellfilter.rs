@@ -0,0 +1,63 @@
+use super::Filter;
+use std::sync::Arc;
+
+/**
+ * Type for a Mitchell–Netravali filter, a cubic filter with negative lobes that trades off
+ * blurring against ringing.
+ */
+#[derive(Debug)]
+pub struct MitchellFilter {
+    radius: f64,
+    b: f64,
+    c: f64,
+}
+
+impl MitchellFilter {
+    /**
+     * Creates a Mitchell–Netravali filter.  The recommended parameters are `b` = `c` = 1/3.
+     */
+    #[must_use]
+    pub fn new(radius: f64, b: f64, c: f64) -> Self {
+        assert!(radius > 0.0);
+        Self { radius, b, c }
+    }
+
+    #[must_use]
+    pub fn new_filter(radius: f64, b: f64, c: f64) -> Arc<dyn Filter> {
+        Arc::new(Self::new(radius, b, c))
+    }
+
+    /**
+     * Evaluates the one-dimensional filter, with `x` scaled to the range [−1, 1].
+     */
+    #[must_use]
+    fn mitchell(&self, x: f64) -> f64 {
+        let Self { b, c, .. } = *self;
+        let x = (2.0 * x).abs();
+        let x2 = x * x;
+        let x3 = x2 * x;
+
+        let y = if x > 2.0 {
+            0.0
+        } else if x > 1.0 {
+            (-b - 6.0 * c) * x3
+                + (6.0 * b + 30.0 * c) * x2
+                + (-12.0 * b - 48.0 * c) * x
+                + (8.0 * b + 24.0 * c)
+        } else {
+            (12.0 - 9.0 * b - 6.0 * c) * x3 + (-18.0 + 12.0 * b + 6.0 * c) * x2 + (6.0 - 2.0 * b)
+        };
+
+        y / 6.0
+    }
+}
+
+impl Filter for MitchellFilter {
+    fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    fn evaluate(&self, x: f64, y: f64) -> f64 {
+        self.mitchell(x / self.radius) * self.mitchell(y / self.radius)
+    }
+}
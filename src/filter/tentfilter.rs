@@ -0,0 +1,34 @@
+use super::Filter;
+use std::sync::Arc;
+
+/**
+ * Type for a tent (triangle) filter, whose weight falls linearly to zero at its radius.
+ */
+#[derive(Debug)]
+pub struct TentFilter {
+    radius: f64,
+}
+
+impl TentFilter {
+    #[must_use]
+    pub fn new(radius: f64) -> Self {
+        assert!(radius > 0.0);
+        Self { radius }
+    }
+
+    #[must_use]
+    pub fn new_filter(radius: f64) -> Arc<dyn Filter> {
+        Arc::new(Self::new(radius))
+    }
+}
+
+impl Filter for TentFilter {
+    fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    fn evaluate(&self, x: f64, y: f64) -> f64 {
+        let r = self.radius;
+        (r - x.abs()).max(0.0) * (r - y.abs()).max(0.0)
+    }
+}
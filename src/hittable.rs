@@ -4,6 +4,7 @@ mod block;
 mod bvhnode;
 mod constantmedium;
 mod hitrecord;
+mod moving;
 mod rotate;
 mod sphere;
 mod translate;
@@ -14,6 +15,7 @@ pub use block::Block;
 pub use bvhnode::BvhNode;
 pub use constantmedium::ConstantMedium;
 pub use hitrecord::HitRecord;
+pub use moving::{MotionKey, Moving};
 pub use rotate::{RotateX, RotateY, RotateZ};
 pub use sphere::{MovingSphere, Sphere};
 use std::{fmt::Debug, ops::Range, sync::Arc};
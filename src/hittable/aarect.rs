@@ -120,9 +120,19 @@ impl Hittable for XyRect {
         let v = (y - self.yr.start) / (self.yr.end - self.yr.start);
         let outward_normal = Vec3(0.0, 0.0, 1.0);
         let p = r.at(t);
+        let tangent = Vec3(1.0, 0.0, 0.0);
         let material = Arc::clone(&self.material);
 
-        Some(HitRecord::new(r, p, outward_normal, t, u, v, material))
+        Some(HitRecord::new(
+            r,
+            p,
+            outward_normal,
+            tangent,
+            t,
+            u,
+            v,
+            material,
+        ))
     }
 
     fn bounding_box(&self, _tr: Range<f64>) -> Aabb {
@@ -153,9 +163,19 @@ impl Hittable for XzRect {
         let v = (z - self.zr.start) / (self.zr.end - self.zr.start);
         let outward_normal = Vec3(0.0, 1.0, 0.0);
         let p = r.at(t);
+        let tangent = Vec3(1.0, 0.0, 0.0);
         let material = Arc::clone(&self.material);
 
-        Some(HitRecord::new(r, p, outward_normal, t, u, v, material))
+        Some(HitRecord::new(
+            r,
+            p,
+            outward_normal,
+            tangent,
+            t,
+            u,
+            v,
+            material,
+        ))
     }
 
     fn bounding_box(&self, _tr: Range<f64>) -> Aabb {
@@ -186,9 +206,19 @@ impl Hittable for YzRect {
         let v = (z - self.zr.start) / (self.zr.end - self.zr.start);
         let outward_normal = Vec3(1.0, 0.0, 0.0);
         let p = r.at(t);
+        let tangent = Vec3(0.0, 1.0, 0.0);
         let material = Arc::clone(&self.material);
 
-        Some(HitRecord::new(r, p, outward_normal, t, u, v, material))
+        Some(HitRecord::new(
+            r,
+            p,
+            outward_normal,
+            tangent,
+            t,
+            u,
+            v,
+            material,
+        ))
     }
 
     fn bounding_box(&self, _tr: Range<f64>) -> Aabb {
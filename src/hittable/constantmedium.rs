@@ -74,6 +74,7 @@ impl Hittable for ConstantMedium {
                         }
 
                         let normal = Vec3(1.0, 0.0, 0.0); // Arbitrary.
+                        let tangent = Vec3(0.0, 1.0, 0.0); // Arbitrary.
                         let u = 0.0; // Arbitrary.
                         let v = 0.0; // Arbitrary.
 
@@ -81,6 +82,7 @@ impl Hittable for ConstantMedium {
                             r,
                             p,
                             normal,
+                            tangent,
                             t,
                             u,
                             v,
@@ -3,11 +3,15 @@ use std::sync::Arc;
 
 /**
  * Type for recording a ray hit.
+ *
+ * Along with the normal, the record holds a tangent frame at the hit: the tangent follows the
+ * direction of increasing `u`, and the bitangent completes a right-handed frame with the normal.
  */
 #[derive(Clone, Debug)]
 pub struct HitRecord {
     p: Vec3,
     normal: Vec3,
+    tangent: Vec3,
     t: f64,
     u: f64,
     v: f64,
@@ -16,12 +20,18 @@ pub struct HitRecord {
 }
 
 impl HitRecord {
-    #[allow(clippy::many_single_char_names)]
+    /**
+     * Creates a record of a hit by ray `r` at point `p` and parameter `t`.  `tangent` is the
+     * direction in which `u` increases; it need not be of unit length or perpendicular to the
+     * normal.
+     */
+    #[allow(clippy::many_single_char_names, clippy::too_many_arguments)]
     #[must_use]
     pub fn new(
         r: &Ray,
         p: Vec3,
         outward_normal: Vec3,
+        tangent: Vec3,
         t: f64,
         u: f64,
         v: f64,
@@ -34,9 +44,24 @@ impl HitRecord {
             -outward_normal
         };
 
+        // Make the tangent perpendicular to the normal, choosing any perpendicular direction if
+        // the parameterisation is degenerate (at the poles of a sphere, for example).
+        let tangent = tangent - tangent.dot(normal) * normal;
+        let tangent = if tangent.is_near_zero() {
+            let axis = if normal.x().abs() > 0.9 {
+                Vec3(0.0, 1.0, 0.0)
+            } else {
+                Vec3(1.0, 0.0, 0.0)
+            };
+            normal.cross(axis).unit()
+        } else {
+            tangent.unit()
+        };
+
         Self {
             p,
             normal,
+            tangent,
             t,
             u,
             v,
@@ -55,6 +80,16 @@ impl HitRecord {
         self.normal
     }
 
+    #[must_use]
+    pub const fn tangent(&self) -> Vec3 {
+        self.tangent
+    }
+
+    #[must_use]
+    pub fn bitangent(&self) -> Vec3 {
+        self.normal.cross(self.tangent)
+    }
+
     #[must_use]
     pub const fn t(&self) -> f64 {
         self.t
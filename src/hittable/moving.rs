@@ -0,0 +1,240 @@
+use super::{Aabb, HitRecord, Hittable};
+use crate::{Quaternion, Ray, Vec3};
+use std::{ops::Range, sync::Arc};
+
+/**
+ * Type for representing the transformation of a moving object at one instant.
+ */
+#[derive(Clone, Copy, Debug)]
+pub struct MotionKey {
+    time: f64,
+    translation: Vec3,
+    rotation: Quaternion,
+    scale: Vec3,
+}
+
+/**
+ * Wrapper for hittable objects that move, rotate and change size over time.
+ *
+ * At any time, the object is scaled, then rotated, then translated.  The transformation is
+ * interpolated between keyframes: linearly for translation and scale, and by spherical linear
+ * interpolation for rotation.  Before the first and after the last keyframe, the object is held
+ * still.
+ */
+#[derive(Debug)]
+pub struct Moving {
+    object: Arc<dyn Hittable>,
+    keys: Vec<MotionKey>,
+}
+
+impl MotionKey {
+    #[must_use]
+    pub fn new(time: f64, translation: Vec3, rotation: Quaternion, scale: Vec3) -> Self {
+        assert!(scale.x() != 0.0 && scale.y() != 0.0 && scale.z() != 0.0);
+        assert!(rotation.dot(rotation) > 0.0);
+        Self {
+            time,
+            translation,
+            rotation: rotation.unit(),
+            scale,
+        }
+    }
+
+    #[must_use]
+    pub const fn time(&self) -> f64 {
+        self.time
+    }
+
+    #[must_use]
+    pub const fn translation(&self) -> Vec3 {
+        self.translation
+    }
+
+    #[must_use]
+    pub const fn rotation(&self) -> Quaternion {
+        self.rotation
+    }
+
+    #[must_use]
+    pub const fn scale(&self) -> Vec3 {
+        self.scale
+    }
+
+    /**
+     * Transforms a point from object space to world space.
+     */
+    #[must_use]
+    fn point_to_world(&self, p: Vec3) -> Vec3 {
+        self.translation + self.rotation.rotate(self.scale * p)
+    }
+
+    /**
+     * Transforms a ray from world space to object space.
+     */
+    #[must_use]
+    fn ray_to_object(&self, r: &Ray) -> Ray {
+        let inverse = self.rotation.conjugate();
+        let origin = inverse.rotate(r.origin() - self.translation) / self.scale;
+        let direction = inverse.rotate(r.direction()) / self.scale;
+        Ray::new(origin, direction, r.time())
+    }
+
+    /**
+     * Transforms a direction, such as a surface tangent, from object space to world space.
+     */
+    #[must_use]
+    fn vector_to_world(&self, v: Vec3) -> Vec3 {
+        self.rotation.rotate(self.scale * v)
+    }
+
+    /**
+     * Transforms a surface normal from object space to world space.
+     */
+    #[must_use]
+    fn normal_to_world(&self, n: Vec3) -> Vec3 {
+        self.rotation.rotate(n / self.scale).unit()
+    }
+}
+
+impl Moving {
+    #[must_use]
+    pub fn new(object: Arc<dyn Hittable>, mut keys: Vec<MotionKey>) -> Self {
+        assert!(!keys.is_empty());
+        keys.sort_by(|a, b| {
+            a.time
+                .partial_cmp(&b.time)
+                .expect("unexpected NaN in keyframe time")
+        });
+
+        // A scale changing sign between keyframes would pass through zero, collapsing the object.
+        for pair in keys.windows(2) {
+            let (a, b) = (pair[0].scale, pair[1].scale);
+            assert!(a.x() * b.x() > 0.0 && a.y() * b.y() > 0.0 && a.z() * b.z() > 0.0);
+        }
+        Self { object, keys }
+    }
+
+    #[must_use]
+    pub fn new_hittable(object: Arc<dyn Hittable>, keys: Vec<MotionKey>) -> Arc<dyn Hittable> {
+        Arc::new(Self::new(object, keys))
+    }
+
+    /**
+     * Returns the interpolated transformation at time `time`.
+     */
+    #[must_use]
+    fn key_at(&self, time: f64) -> MotionKey {
+        let n = self.keys.len();
+
+        // Index of the first keyframe after `time`.
+        let i = self.keys.partition_point(|key| key.time <= time);
+        if i == 0 {
+            return MotionKey {
+                time,
+                ..self.keys[0]
+            };
+        }
+        if i == n {
+            return MotionKey {
+                time,
+                ..self.keys[n - 1]
+            };
+        }
+
+        let k1 = &self.keys[i - 1];
+        let k2 = &self.keys[i];
+        let a = (time - k1.time) / (k2.time - k1.time);
+
+        MotionKey {
+            time,
+            translation: k1.translation + a * (k2.translation - k1.translation),
+            rotation: k1.rotation.slerp(k2.rotation, a),
+            scale: k1.scale + a * (k2.scale - k1.scale),
+        }
+    }
+}
+
+impl Hittable for Moving {
+    fn hit(&self, r: &Ray, tr: Range<f64>) -> Option<HitRecord> {
+        let key = self.key_at(r.time());
+        let object_r = key.ray_to_object(r);
+
+        self.object.hit(&object_r, tr).map(|rec| {
+            // Recover the outward normal so that `HitRecord::new` gets the right face.
+            let outward_normal = if rec.front_face() {
+                rec.normal()
+            } else {
+                -rec.normal()
+            };
+
+            HitRecord::new(
+                r,
+                key.point_to_world(rec.p()),
+                key.normal_to_world(outward_normal),
+                key.vector_to_world(rec.tangent()),
+                rec.t(),
+                rec.u(),
+                rec.v(),
+                rec.material(),
+            )
+        })
+    }
+
+    fn bounding_box(&self, tr: Range<f64>) -> Aabb {
+        let object_box = self.object.bounding_box(tr.clone());
+        let mn = object_box.minimum();
+        let mx = object_box.maximum();
+        let corners = [
+            Vec3(mn.x(), mn.y(), mn.z()),
+            Vec3(mn.x(), mn.y(), mx.z()),
+            Vec3(mn.x(), mx.y(), mn.z()),
+            Vec3(mn.x(), mx.y(), mx.z()),
+            Vec3(mx.x(), mn.y(), mn.z()),
+            Vec3(mx.x(), mn.y(), mx.z()),
+            Vec3(mx.x(), mx.y(), mn.z()),
+            Vec3(mx.x(), mx.y(), mx.z()),
+        ];
+
+        // Split the time range at the keyframes, so that the motion within each piece is a single
+        // interpolation between two transformations.
+        let mut times = vec![tr.start];
+        times.extend(
+            self.keys
+                .iter()
+                .map(|key| key.time)
+                .filter(|&time| tr.start < time && time < tr.end),
+        );
+        times.push(tr.end);
+
+        times
+            .windows(2)
+            .map(|times| {
+                let key0 = self.key_at(times[0]);
+                let key1 = self.key_at(times[1]);
+
+                if key0.rotation.dot(key1.rotation).abs() > 1.0 - 1e-12 {
+                    // Without rotation, every point of the object moves in a straight line, so it
+                    // stays within the boxes at the start and end of the piece.
+                    [key0, key1]
+                        .iter()
+                        .flat_map(|key| corners.iter().map(move |&c| key.point_to_world(c)))
+                        .map(|p| Aabb::new(p, p))
+                        .reduce(Aabb::surrounding_box)
+                        .unwrap()
+                } else {
+                    // Whatever the rotation, the object stays within a sphere about its origin,
+                    // whose radius is largest at one end of the piece.
+                    let radius = [key0, key1]
+                        .iter()
+                        .flat_map(|key| corners.iter().map(move |&c| (key.scale * c).length()))
+                        .fold(0.0, f64::max);
+                    let radius = Vec3(radius, radius, radius);
+                    let box0 = Aabb::new(key0.translation - radius, key0.translation + radius);
+                    let box1 = Aabb::new(key1.translation - radius, key1.translation + radius);
+                    Aabb::surrounding_box(box0, box1)
+                }
+            })
+            .reduce(Aabb::surrounding_box)
+            .unwrap()
+    }
+}
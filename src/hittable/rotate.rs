@@ -234,6 +234,7 @@ impl Hittable for RotateX {
         self.object.hit(&rotated_r, tr).map(|rec| {
             let Vec3(px, p1y, p1z) = rec.p();
             let Vec3(nx, n1y, n1z) = rec.normal();
+            let Vec3(tx, t1y, t1z) = rec.tangent();
 
             let p2y = self.cos_theta * p1y + self.sin_theta * p1z;
             let p2z = -self.sin_theta * p1y + self.cos_theta * p1z;
@@ -241,13 +242,18 @@ impl Hittable for RotateX {
             let n2y = self.cos_theta * n1y + self.sin_theta * n1z;
             let n2z = -self.sin_theta * n1y + self.cos_theta * n1z;
 
+            let t2y = self.cos_theta * t1y + self.sin_theta * t1z;
+            let t2z = -self.sin_theta * t1y + self.cos_theta * t1z;
+
             let p = Vec3(px, p2y, p2z);
             let normal = Vec3(nx, n2y, n2z);
+            let tangent = Vec3(tx, t2y, t2z);
 
             HitRecord::new(
                 &rotated_r,
                 p,
                 normal,
+                tangent,
                 rec.t(),
                 rec.u(),
                 rec.v(),
@@ -280,6 +286,7 @@ impl Hittable for RotateY {
         self.object.hit(&rotated_r, tr).map(|rec| {
             let Vec3(p1x, py, p1z) = rec.p();
             let Vec3(n1x, ny, n1z) = rec.normal();
+            let Vec3(t1x, ty, t1z) = rec.tangent();
 
             let p2x = self.cos_theta * p1x + self.sin_theta * p1z;
             let p2z = -self.sin_theta * p1x + self.cos_theta * p1z;
@@ -287,13 +294,18 @@ impl Hittable for RotateY {
             let n2x = self.cos_theta * n1x + self.sin_theta * n1z;
             let n2z = -self.sin_theta * n1x + self.cos_theta * n1z;
 
+            let t2x = self.cos_theta * t1x + self.sin_theta * t1z;
+            let t2z = -self.sin_theta * t1x + self.cos_theta * t1z;
+
             let p = Vec3(p2x, py, p2z);
             let normal = Vec3(n2x, ny, n2z);
+            let tangent = Vec3(t2x, ty, t2z);
 
             HitRecord::new(
                 &rotated_r,
                 p,
                 normal,
+                tangent,
                 rec.t(),
                 rec.u(),
                 rec.v(),
@@ -326,6 +338,7 @@ impl Hittable for RotateZ {
         self.object.hit(&rotated_r, tr).map(|rec| {
             let Vec3(p1x, p1y, pz) = rec.p();
             let Vec3(n1x, n1y, nz) = rec.normal();
+            let Vec3(t1x, t1y, tz) = rec.tangent();
 
             let p2x = self.cos_theta * p1x + self.sin_theta * p1y;
             let p2y = -self.sin_theta * p1x + self.cos_theta * p1y;
@@ -333,13 +346,18 @@ impl Hittable for RotateZ {
             let n2x = self.cos_theta * n1x + self.sin_theta * n1y;
             let n2y = -self.sin_theta * n1x + self.cos_theta * n1y;
 
+            let t2x = self.cos_theta * t1x + self.sin_theta * t1y;
+            let t2y = -self.sin_theta * t1x + self.cos_theta * t1y;
+
             let p = Vec3(p2x, p2y, pz);
             let normal = Vec3(n2x, n2y, nz);
+            let tangent = Vec3(t2x, t2y, tz);
 
             HitRecord::new(
                 &rotated_r,
                 p,
                 normal,
+                tangent,
                 rec.t(),
                 rec.u(),
                 rec.v(),
@@ -110,9 +110,19 @@ impl Hittable for Sphere {
         let p = r.at(t);
         let outward_normal = (p - self.centre) / self.radius;
         let (u, v) = get_sphere_uv(outward_normal);
+        let tangent = get_sphere_tangent(outward_normal);
         let material = Arc::clone(&self.material);
 
-        Some(HitRecord::new(r, p, outward_normal, t, u, v, material))
+        Some(HitRecord::new(
+            r,
+            p,
+            outward_normal,
+            tangent,
+            t,
+            u,
+            v,
+            material,
+        ))
     }
 
     fn bounding_box(&self, _tr: Range<f64>) -> Aabb {
@@ -154,9 +164,19 @@ impl Hittable for MovingSphere {
         let p = r.at(t);
         let outward_normal = (p - centre) / self.radius;
         let (u, v) = get_sphere_uv(outward_normal);
+        let tangent = get_sphere_tangent(outward_normal);
         let material = Arc::clone(&self.material);
 
-        Some(HitRecord::new(r, p, outward_normal, t, u, v, material))
+        Some(HitRecord::new(
+            r,
+            p,
+            outward_normal,
+            tangent,
+            t,
+            u,
+            v,
+            material,
+        ))
     }
 
     fn bounding_box(&self, tr: Range<f64>) -> Aabb {
@@ -192,3 +212,12 @@ fn get_sphere_uv(p: Vec3) -> (f64, f64) {
 
     (u, v)
 }
+
+/**
+ * Returns the direction in which `u`, as returned by `get_sphere_uv`, increases at point `p` on
+ * the unit sphere.
+ */
+#[must_use]
+fn get_sphere_tangent(p: Vec3) -> Vec3 {
+    Vec3(p.z(), 0.0, -p.x())
+}
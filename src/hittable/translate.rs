@@ -31,6 +31,7 @@ impl Hittable for Translate {
                 &moved_r,
                 rec.p() + self.offset,
                 rec.normal(),
+                rec.tangent(),
                 rec.t(),
                 rec.u(),
                 rec.v(),
@@ -1,93 +1,308 @@
+mod background;
 mod camera;
 mod colour;
+mod filter;
 mod hittable;
+mod light;
 mod material;
 mod perlin;
+mod quaternion;
 mod ray;
+mod sampler;
+mod spectrum;
 mod texture;
 mod vec3;
-pub use camera::Camera;
+pub use background::{
+    Background, ConstantBackground, EnvironmentMap, GradientBackground, Sky, TextureBackground,
+};
+pub use camera::{
+    Aperture, Camera, CameraKey, CameraRig, FocusTarget, Interpolation, Stereo, StereoFormat,
+};
 pub use colour::Colour;
+pub use filter::{BoxFilter, Filter, GaussianFilter, LanczosFilter, MitchellFilter, TentFilter};
 use hittable::HitRecord;
 pub use hittable::{
-    Block, BvhNode, ConstantMedium, Hittable, MovingSphere, RotateX, RotateY, RotateZ, Sphere,
-    Translate, XyRect, XzRect, YzRect,
+    Block, BvhNode, ConstantMedium, Hittable, MotionKey, Moving, MovingSphere, RotateX, RotateY,
+    RotateZ, Sphere, Translate, XyRect, XzRect, YzRect,
 };
+use image::{ImageFormat, Rgb, RgbImage};
+pub use light::{DirectionalLight, IesProfile, Light, PointLight, SpotLight};
 pub use material::{
-    Dielectric, DiffuseLight, Isotropic, Lambertian0, Lambertian1, Lambertian2, Material, Metal,
+    AddMaterial, Coated, Conductor, ConductorPreset, Dielectric, DiffuseLight, Isotropic,
+    Lambertian0, Lambertian1, Lambertian2, Material, Metal, Mix, OrenNayar, Principled,
+    RefractiveIndex, RoughDielectric,
 };
 use perlin::Perlin;
-use rand::prelude::*;
+pub use quaternion::Quaternion;
 use ray::Ray;
-use std::{convert::TryFrom, error::Error, io::prelude::*, panic, sync::Arc, thread};
+pub use sampler::{
+    BlueNoiseSampler, HaltonSampler, IndependentSampler, Sampler, SobolSampler, StratifiedSampler,
+};
+use spectrum::SampledSpectrum;
+use std::{
+    convert::TryFrom,
+    error::Error,
+    ffi::OsString,
+    fs,
+    io::prelude::*,
+    ops::{Add, Mul, Range, RangeInclusive},
+    panic,
+    path::{Path, PathBuf},
+    sync::Arc,
+    thread,
+};
 pub use texture::{Chequer, Image, Noise, SolidColour, Texture};
 pub use vec3::Vec3;
 
+/**
+ * The sum of the weighted samples contributing to a pixel, and the sum of their weights.
+ */
+type PixelSum = (Colour, f64);
+
+/**
+ * Trait for the light carried along rays: a colour, or when rendering spectrally the spectrum at
+ * the wavelengths the ray carries.
+ */
+trait Radiance: Copy + Add<Output = Self> + Mul<Output = Self> + Mul<f64, Output = Self> {
+    /**
+     * Returns the quantity for `colour`, such as a reflectance or the light from a source, as
+     * seen by the ray `r`.
+     */
+    fn new(colour: Colour, r: &Ray) -> Self;
+
+    /**
+     * Returns the quantity for light that can only follow a dispersed path at the ray’s hero
+     * wavelength.
+     */
+    fn hero(self) -> Self;
+}
+
+impl Radiance for Colour {
+    fn new(colour: Colour, _r: &Ray) -> Self {
+        colour
+    }
+
+    fn hero(self) -> Self {
+        self
+    }
+}
+
+impl Radiance for SampledSpectrum {
+    fn new(colour: Colour, r: &Ray) -> Self {
+        match r.wavelengths() {
+            Some(wavelengths) => Self::new(colour, wavelengths),
+            None => Self::default(),
+        }
+    }
+
+    fn hero(self) -> Self {
+        self.hero()
+    }
+}
+
 /**
  * Calculates the colour of a ray of light.
+ *
+ * At each surface that scatters light diffusely, the background and the lights are also sampled
+ * directly, and the two estimates of the light from the background are combined by multiple
+ * importance sampling.
+ * `bsdf_pdf` is the probability density with which the ray’s direction was chosen by such a
+ * surface, or `None` if the ray was not scattered diffusely.
+ *
+ * Colours from the scene are converted to the radiance type `R` as they are met, so that when
+ * rendering spectrally each is upsampled to a spectrum before being multiplied by the others.
  */
 #[must_use]
-fn ray_colour(r: &Ray, background: Colour, world: &dyn Hittable, depth: u32) -> Colour {
+fn ray_colour<R: Radiance>(
+    r: &Ray,
+    background: &dyn Background,
+    lights: &[Arc<dyn Light>],
+    world: &dyn Hittable,
+    sampler: &mut dyn Sampler,
+    depth: u32,
+    bsdf_pdf: Option<f64>,
+) -> R {
     // If we’ve exceeded the ray bounce limit, no more light is gathered.
     if depth == 0 {
-        return Colour(0.0, 0.0, 0.0);
+        return R::new(Colour(0.0, 0.0, 0.0), r);
     }
 
     if let Some(rec) = world.hit(r, 0.001..f64::INFINITY) {
-        let emitted = rec.material_ref().emitted(rec.u(), rec.v(), rec.p());
+        let material = rec.material_ref();
+        let emitted = R::new(material.emitted(r, &rec), r);
+
+        let light_sample = sampler.get_2d();
+        let direct = sample_background::<R>(r, &rec, background, world, light_sample)
+            + sample_lights::<R>(r, &rec, lights, world);
 
-        if let Some((attenuation, scattered)) = rec.material_ref().scatter(r, &rec) {
-            emitted + attenuation * ray_colour(&scattered, background, world, depth - 1)
+        // Light from the hit is absorbed by any medium the ray has travelled through.
+        let transmittance = R::new(material.transmittance(r, &rec), r);
+        let colour = if let Some((attenuation, scattered)) = material.scatter(r, &rec, sampler) {
+            let scattered = scattered.with_wavelengths(r.wavelengths());
+            let bsdf_pdf = material
+                .eval(r, &rec, scattered.direction())
+                .map(|(_, pdf)| pdf);
+            let scattered_colour = R::new(attenuation, r)
+                * ray_colour::<R>(
+                    &scattered,
+                    background,
+                    lights,
+                    world,
+                    sampler,
+                    depth - 1,
+                    bsdf_pdf,
+                );
+
+            // Only the hero wavelength can follow a path that depends on the wavelength.
+            let scattered_colour = if material.is_dispersive() {
+                scattered_colour.hero()
+            } else {
+                scattered_colour
+            };
+            emitted + direct + scattered_colour
         } else {
-            emitted
+            emitted + direct
+        };
+        transmittance * colour
+    } else {
+        // If the ray hits nothing, return the background colour, weighted against the chance of
+        // having sampled it directly.
+        let value = R::new(background.value(r.direction()), r);
+        match bsdf_pdf {
+            Some(bsdf_pdf) => value * power_heuristic(bsdf_pdf, background.pdf(r.direction())),
+            None => value,
         }
+    }
+}
+
+/**
+ * Estimates the light from the background scattered at `rec` back along the ray `r`, by sampling
+ * a direction towards the background using `light_sample`.
+ */
+#[must_use]
+fn sample_background<R: Radiance>(
+    r: &Ray,
+    rec: &HitRecord,
+    background: &dyn Background,
+    world: &dyn Hittable,
+    light_sample: (f64, f64),
+) -> R {
+    let black = R::new(Colour(0.0, 0.0, 0.0), r);
+
+    let (direction, value, light_pdf) = match background.sample(light_sample) {
+        Some(sample) => sample,
+        None => return black,
+    };
+    let (attenuation, bsdf_pdf) = match rec.material_ref().eval(r, rec, direction) {
+        Some(eval) => eval,
+        None => return black,
+    };
+
+    let shadow = Ray::new(rec.p(), direction, r.time());
+    if world.hit(&shadow, 0.001..f64::INFINITY).is_some() {
+        return black;
+    }
+
+    R::new(attenuation, r) * R::new(value, r) * (power_heuristic(light_pdf, bsdf_pdf) / light_pdf)
+}
+
+/**
+ * Calculates the light from `lights` scattered at `rec` back along the ray `r`.
+ */
+#[must_use]
+fn sample_lights<R: Radiance>(
+    r: &Ray,
+    rec: &HitRecord,
+    lights: &[Arc<dyn Light>],
+    world: &dyn Hittable,
+) -> R {
+    let mut colour = R::new(Colour(0.0, 0.0, 0.0), r);
+
+    for light in lights {
+        let (direction, distance, irradiance) = match light.illuminate(rec.p()) {
+            Some(illumination) => illumination,
+            None => continue,
+        };
+        let (attenuation, _) = match rec.material_ref().eval(r, rec, direction) {
+            Some(eval) => eval,
+            None => continue,
+        };
+
+        let shadow = Ray::new(rec.p(), direction, r.time());
+        if world.hit(&shadow, 0.001..distance).is_none() {
+            colour = colour + R::new(attenuation, r) * R::new(irradiance, r);
+        }
+    }
+
+    colour
+}
+
+/**
+ * Returns the weight for a sample chosen with probability density `pdf`, when it could also have
+ * been chosen with density `other_pdf` by another strategy (Veach’s power heuristic).
+ */
+#[must_use]
+fn power_heuristic(pdf: f64, other_pdf: f64) -> f64 {
+    let a = pdf * pdf;
+    let b = other_pdf * other_pdf;
+    if a + b > 0.0 {
+        a / (a + b)
     } else {
-        // If the ray hits nothing, return the background colour.
-        background
+        0.0
     }
 }
 
 /**
  * Renders a scene.
  *
+ * Each sample is splatted onto all the pixels within the radius of the reconstruction filter.
+ * Returns, for each pixel, the sum of the weighted sample colours and the sum of the weights.
+ *
  * # Parameters
  *
  * * `world` contains the hittable objects in the scene.
- * * `background` is the background colour.
+ * * `background` is the light from the surroundings.
+ * * `lights` are the light sources that are sampled explicitly.
  * * `image_width` and `image_height` are the image dimesions, in pixels.
- * * `samples_per_pixel` is the number of samples per pixel.
+ * * `samples` is the range of sample indices to render, out of `samples_per_pixel` in total, so
+ *   that threads sharing the work take different samples from the sampler.
  * * `max_depth` is the recursion limit for ray reflections.
  * * `cam` is the camera.
+ * * `filter` is the pixel reconstruction filter.
+ * * `sampler` generates the sample values.
+ * * If `spectral` is `true`, each sample traces several wavelengths of light, with spectra
+ *   upsampled from the colours in the scene.
  * * If `log` is `true`, progress is reported to the standard error stream.
  */
-#[allow(clippy::too_many_arguments)]
+#[allow(clippy::too_many_arguments, clippy::many_single_char_names)]
 fn render(
     world: Arc<dyn Hittable>,
-    background: Colour,
+    background: Arc<dyn Background>,
+    lights: Arc<[Arc<dyn Light>]>,
     image_width: u32,
     image_height: u32,
+    samples: Range<u32>,
     samples_per_pixel: u32,
     max_depth: u32,
     cam: Arc<Camera>,
+    filter: Arc<dyn Filter>,
+    mut sampler: Box<dyn Sampler>,
+    spectral: bool,
     log: bool,
-) -> Result<Box<[Colour]>, Box<dyn Error + Send + Sync>> {
-    #![allow(clippy::many_single_char_names)]
-
+) -> Result<Box<[PixelSum]>, Box<dyn Error + Send + Sync>> {
     assert!(image_width > 1);
     assert!(image_height > 1);
-    assert!(samples_per_pixel > 0);
+    assert!(samples.end <= samples_per_pixel);
     assert!(max_depth > 0);
 
-    // Initialize random number generator.
-    let mut rand_eng = thread_rng();
-
-    // Render.
-
-    let mut pixels =
-        Vec::with_capacity(usize::try_from(image_width)? * usize::try_from(image_height)?);
+    let mut pixels = vec![
+        (Colour(0.0, 0.0, 0.0), 0.0);
+        usize::try_from(image_width)? * usize::try_from(image_height)?
+    ];
 
     let width_scale = f64::from(image_width - 1);
     let height_scale = f64::from(image_height - 1);
+    let radius = filter.radius();
 
     for j in (0..image_height).rev() {
         if log {
@@ -98,22 +313,65 @@ fn render(
             );
         }
 
-        let j = f64::from(j);
-
         for i in 0..image_width {
-            let i = f64::from(i);
+            for index in samples.clone() {
+                sampler.start_sample((i, j), index, samples_per_pixel);
 
-            let mut pixel_colour = Colour(0.0, 0.0, 0.0);
-            for _ in 0..samples_per_pixel {
-                let u = (i + rand_eng.gen::<f64>()) / width_scale;
-                let v = (j + rand_eng.gen::<f64>()) / height_scale;
+                let (dx, dy) = sampler.get_2d();
+                let x = f64::from(i) + dx;
+                let y = f64::from(j) + dy;
+                let u = x / width_scale;
+                let v = y / height_scale;
 
-                let r = cam.get_ray(u, v);
+                // Rays blocked by vignetting contribute black.
+                let r = cam.get_ray(u, v, sampler.as_mut());
+                let sample_colour = match r {
+                    Some(r) if spectral => {
+                        // The spectrum is projected onto the film as a colour.
+                        let (wavelengths, pdfs) = spectrum::sample_wavelengths(sampler.get_1d());
+                        ray_colour::<SampledSpectrum>(
+                            &r.with_wavelengths(Some(wavelengths)),
+                            background.as_ref(),
+                            &lights,
+                            world.as_ref(),
+                            sampler.as_mut(),
+                            max_depth,
+                            None,
+                        )
+                        .to_colour(wavelengths, pdfs)
+                    }
+                    Some(r) => ray_colour::<Colour>(
+                        &r,
+                        background.as_ref(),
+                        &lights,
+                        world.as_ref(),
+                        sampler.as_mut(),
+                        max_depth,
+                        None,
+                    ),
+                    None => Colour(0.0, 0.0, 0.0),
+                };
 
-                pixel_colour += ray_colour(&r, background, world.as_ref(), max_depth);
-            }
+                // Splat the sample onto the pixels whose centres are within the filter radius, and
+                // within the same view, so that each eye of a stereo camera is filtered on its own.
+                let (s_bounds, t_bounds) = cam.view_bounds(u, v);
+                let (k0, k1) = pixel_range(x, radius, s_bounds, width_scale, image_width);
+                let (l0, l1) = pixel_range(y, radius, t_bounds, height_scale, image_height);
 
-            pixels.push(pixel_colour);
+                for l in l0..=l1 {
+                    let dy = y - (f64::from(l) + 0.5);
+                    let row = (image_height - 1 - l) * image_width;
+                    for k in k0..=k1 {
+                        let dx = x - (f64::from(k) + 0.5);
+                        let weight = filter.evaluate(dx, dy);
+                        if weight != 0.0 {
+                            let (colour_sum, weight_sum) = &mut pixels[(row + k) as usize];
+                            *colour_sum += weight * sample_colour;
+                            *weight_sum += weight;
+                        }
+                    }
+                }
+            }
         }
     }
 
@@ -124,36 +382,80 @@ fn render(
     Ok(pixels.into_boxed_slice())
 }
 
+/**
+ * Returns the first and last of the `count` pixels along an axis whose centres are within
+ * `radius` of the sample position `x`, and whose image coordinates, the pixel position divided by
+ * `scale`, are within `bounds`.  The range is empty if the first is after the last.
+ */
+#[must_use]
+fn pixel_range(x: f64, radius: f64, bounds: Range<f64>, scale: f64, count: u32) -> (u32, u32) {
+    let mut first = (x - 0.5 - radius).ceil().max(0.0);
+    let mut last = (x - 0.5 + radius).floor().min(f64::from(count - 1));
+
+    // Pixel centres from the start of the bounds up to, but not including, the end, unless that
+    // is the end of the image.
+    first = first.max((bounds.start * scale - 0.5).ceil());
+    if bounds.end < 1.0 {
+        last = last.min((bounds.end * scale - 0.5).ceil() - 1.0);
+    }
+
+    if last < first {
+        (1, 0)
+    } else {
+        (first as u32, last as u32)
+    }
+}
+
+/**
+ * Returns the colour of a pixel from the sum of its weighted samples and the sum of the weights.
+ */
+#[must_use]
+fn pixel_colour((colour_sum, weight_sum): PixelSum) -> Colour {
+    if weight_sum > 0.0 {
+        colour_sum / weight_sum
+    } else {
+        Colour(0.0, 0.0, 0.0)
+    }
+}
+
+/**
+ * Adjusts the colours of an image so that light of colour temperature `temperature`, in kelvins,
+ * appears neutral.
+ */
+fn apply_white_balance(pixels: &mut [PixelSum], temperature: f64) {
+    let white = Colour::new_blackbody(temperature, true);
+    for (colour_sum, _) in pixels {
+        *colour_sum = colour_sum.white_balance(white);
+    }
+}
+
 /**
  * Writes an image file.
  *
  * # Parameters
  *
  * * `output` is the stream to write the generated image to.
- * * `pixels` is the image data.
+ * * `pixels` is the image data, as weighted sums of samples.
  * * `image_width` and `image_height` are the image dimesions, in pixels.
- * * `samples_per_pixel` is the number of samples per pixel.
  * * If `log` is `true`, progress is reported to the standard error stream.
  */
 fn write_file(
     output: &mut dyn Write,
-    pixels: &[Colour],
+    pixels: &[PixelSum],
     image_width: u32,
     image_height: u32,
-    samples_per_pixel: u32,
     log: bool,
 ) -> Result<(), Box<dyn Error + Send + Sync>> {
     assert!(image_width > 1);
     assert!(image_height > 1);
-    assert!(samples_per_pixel > 0);
 
     if log {
         eprintln!("Writing output...");
     }
 
     write!(output, "P3\n{} {}\n255\n", image_width, image_height)?;
-    for pixel_colour in pixels {
-        let (ir, ig, ib) = pixel_colour.to_rgb8(samples_per_pixel);
+    for &pixel in pixels {
+        let (ir, ig, ib) = pixel_colour(pixel).to_rgb8(1);
         writeln!(output, "{} {} {}", ir, ig, ib)?;
     }
 
@@ -165,56 +467,80 @@ fn write_file(
 }
 
 /**
- * Runs the program.
+ * Renders a scene using all available processors.
  *
  * # Parameters
  *
  * * `world` contains the hittable objects in the scene.
- * * `background` is the background colour.
+ * * `background` is the light from the surroundings.
+ * * `lights` are the light sources that are sampled explicitly.
  * * `image_width` and `image_height` are the image dimesions, in pixels.
  * * `samples_per_pixel` is the number of samples per pixel.
  * * `max_depth` is the recursion limit for ray reflections.
  * * `cam` is the camera.
- * * `output` is the stream to write the generated image to.
+ * * `filter` is the pixel reconstruction filter.
+ * * `sampler` generates the sample values.  Each thread uses its own copy.
+ * * If `spectral` is `true`, each sample traces several wavelengths of light, with spectra
+ *   upsampled from the colours in the scene.
  * * If `log` is `true`, progress is reported to the standard error stream.
  */
 #[allow(clippy::too_many_arguments)]
-pub fn run(
+fn render_parallel(
     world: Arc<dyn Hittable>,
-    background: Colour,
+    background: Arc<dyn Background>,
+    lights: Arc<[Arc<dyn Light>]>,
     image_width: u32,
     image_height: u32,
     samples_per_pixel: u32,
     max_depth: u32,
     cam: Arc<Camera>,
-    output: &mut dyn Write,
+    filter: Arc<dyn Filter>,
+    sampler: &dyn Sampler,
+    spectral: bool,
     log: bool,
-) -> Result<(), Box<dyn Error + Send + Sync>> {
+) -> Result<Box<[PixelSum]>, Box<dyn Error + Send + Sync>> {
+    assert!(samples_per_pixel > 0);
+
     let num_threads = u32::try_from(num_cpus::get())?;
     assert!(num_threads > 0);
 
     let samples_per_thread = samples_per_pixel / num_threads;
     let remaining_samples = samples_per_pixel % num_threads;
 
+    // This thread takes the first samples; the others take consecutive ranges after them.
+    let mut samples_start = samples_per_thread;
+
     // Spawn threads.
     let mut threads = Vec::with_capacity(num_threads as usize - 1);
     for thread_num in 1..num_threads {
-        let samples_per_pixel = if thread_num <= remaining_samples {
+        let thread_samples = if thread_num <= remaining_samples {
             samples_per_thread + 1
         } else {
             samples_per_thread
         };
+        let samples = samples_start..samples_start + thread_samples;
+        samples_start = samples.end;
+
         let world = Arc::clone(&world);
+        let background = Arc::clone(&background);
+        let lights = Arc::clone(&lights);
         let cam = Arc::clone(&cam);
+        let filter = Arc::clone(&filter);
+        let sampler = sampler.clone_sampler();
         threads.push(thread::spawn(move || {
             render(
                 world,
                 background,
+                lights,
                 image_width,
                 image_height,
+                samples,
                 samples_per_pixel,
                 max_depth,
                 cam,
+                filter,
+                sampler,
+                spectral,
                 false,
             )
         }));
@@ -224,11 +550,16 @@ pub fn run(
     let mut pixels = render(
         world,
         background,
+        lights,
         image_width,
         image_height,
-        samples_per_thread,
+        0..samples_per_thread,
+        samples_per_pixel,
         max_depth,
         cam,
+        filter,
+        sampler.clone_sampler(),
+        spectral,
         log,
     )?;
 
@@ -242,20 +573,330 @@ pub fn run(
             Err(x) => panic::resume_unwind(x),
         };
         assert_eq!(pixels.len(), thread_pixels.len());
-        for (pixel, thread_pixel) in pixels.iter_mut().zip(thread_pixels.iter()) {
-            *pixel += *thread_pixel;
+        for ((colour, weight), (thread_colour, thread_weight)) in
+            pixels.iter_mut().zip(thread_pixels.iter())
+        {
+            *colour += *thread_colour;
+            *weight += *thread_weight;
         }
     }
     if log {
         eprintln!();
     }
 
-    write_file(
-        output,
-        &pixels,
+    Ok(pixels)
+}
+
+/**
+ * Writes an image file in a format determined by the file name extension.
+ *
+ * The image is first written to a temporary file, which is then renamed, so that an interrupted
+ * write never leaves an incomplete file under the final name.
+ *
+ * # Parameters
+ *
+ * * `filename` is the name of the file to write.
+ * * `pixels` is the image data, as weighted sums of samples.
+ * * `image_width` and `image_height` are the image dimesions, in pixels.
+ */
+fn write_image_file(
+    filename: &Path,
+    pixels: &[PixelSum],
+    image_width: u32,
+    image_height: u32,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    assert_eq!(
+        pixels.len(),
+        usize::try_from(image_width)? * usize::try_from(image_height)?
+    );
+
+    let format = match ImageFormat::from_path(filename) {
+        Ok(format) => format,
+        Err(x) => {
+            return Err(format!("cannot write “{}”: {}", filename.display(), x).into());
+        }
+    };
+
+    let image = RgbImage::from_fn(image_width, image_height, |i, j| {
+        let (ir, ig, ib) = pixel_colour(pixels[(j * image_width + i) as usize]).to_rgb8(1);
+        Rgb([ir, ig, ib])
+    });
+
+    let mut temp_name = OsString::from(".");
+    temp_name.push(filename.file_name().unwrap_or_default());
+    let temp_filename = filename.with_file_name(temp_name);
+
+    if let Err(x) = image.save_with_format(&temp_filename, format) {
+        return Err(format!("error writing to “{}”: {}", temp_filename.display(), x).into());
+    }
+    if let Err(x) = fs::rename(&temp_filename, filename) {
+        return Err(format!(
+            "cannot rename output file to “{}”: {}",
+            filename.display(),
+            x
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+/**
+ * Expands a frame number into an output file name pattern, replacing the first `%d` (or, for
+ * padding, `%4d`, or for zero-padding, `%04d`, etc.) in `pattern` with `frame`.  `%%` stands for
+ * a literal `%`, as does any other `%` not starting such a conversion.
+ */
+fn frame_filename(pattern: &str, frame: u32) -> Result<PathBuf, Box<dyn Error + Send + Sync>> {
+    let mut filename = String::with_capacity(pattern.len());
+    let mut converted = false;
+    let mut rest = pattern;
+    while let Some(start) = rest.find('%') {
+        filename.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        if let Some(after) = after.strip_prefix('%') {
+            filename.push('%');
+            rest = after;
+            continue;
+        }
+
+        let digits = after.len() - after.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        if converted || !after[digits..].starts_with('d') {
+            filename.push('%');
+            rest = after;
+            continue;
+        }
+
+        let spec = &after[..digits];
+        let number = if spec.is_empty() {
+            frame.to_string()
+        } else {
+            let width: usize = spec
+                .parse()
+                .map_err(|_| format!("invalid output file name pattern “{}”", pattern))?;
+            if spec.starts_with('0') {
+                format!("{:0width$}", frame, width = width)
+            } else {
+                format!("{:width$}", frame, width = width)
+            }
+        };
+        filename.push_str(&number);
+        converted = true;
+        rest = &after[digits + 1..];
+    }
+    filename.push_str(rest);
+
+    if !converted {
+        return Err(format!("invalid output file name pattern “{}”", pattern).into());
+    }
+    Ok(PathBuf::from(filename))
+}
+
+/**
+ * Runs the program.
+ *
+ * # Parameters
+ *
+ * * `world` contains the hittable objects in the scene.
+ * * `background` is the light from the surroundings.
+ * * `lights` are the light sources that are sampled explicitly.
+ * * `image_width` and `image_height` are the image dimesions, in pixels.
+ * * `samples_per_pixel` is the number of samples per pixel.
+ * * `max_depth` is the recursion limit for ray reflections.
+ * * `cam` is the camera.
+ * * `filter` is the pixel reconstruction filter.
+ * * `sampler` generates the sample values.
+ * * If `spectral` is `true`, each sample traces several wavelengths of light, with spectra
+ *   upsampled from the colours in the scene, so that materials can vary with wavelength.
+ * * `white_balance`, if given, is the colour temperature of the light, in kelvins, to be shown as
+ *   neutral in the output.
+ * * `output` is the stream to write the generated image to.
+ * * If `log` is `true`, progress is reported to the standard error stream.
+ */
+#[allow(clippy::too_many_arguments)]
+pub fn run(
+    world: Arc<dyn Hittable>,
+    background: Arc<dyn Background>,
+    lights: &[Arc<dyn Light>],
+    image_width: u32,
+    image_height: u32,
+    samples_per_pixel: u32,
+    max_depth: u32,
+    cam: Arc<Camera>,
+    filter: Arc<dyn Filter>,
+    sampler: &dyn Sampler,
+    spectral: bool,
+    white_balance: Option<f64>,
+    output: &mut dyn Write,
+    log: bool,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let mut pixels = render_parallel(
+        world,
+        background,
+        Arc::from(lights),
         image_width,
         image_height,
         samples_per_pixel,
+        max_depth,
+        cam,
+        filter,
+        sampler,
+        spectral,
         log,
-    )
+    )?;
+
+    if let Some(temperature) = white_balance {
+        apply_white_balance(&mut pixels, temperature);
+    }
+
+    write_file(output, &pixels, image_width, image_height, log)
+}
+
+/**
+ * Renders a sequence of animation frames, each to its own image file.
+ *
+ * Frame `n` is exposed from time `n / fps` to `(n + 1) / fps`.  For each frame, the camera’s
+ * shutter interval is set accordingly and a bounding volume hierarchy is built over `objects` for
+ * that interval.  Frames whose output file already exists are skipped, so an interrupted job can
+ * be resumed by running it again.
+ *
+ * # Parameters
+ *
+ * * `objects` contains the hittable objects in the scene.
+ * * `background` is the light from the surroundings.
+ * * `lights` are the light sources that are sampled explicitly.
+ * * `image_width` and `image_height` are the image dimesions, in pixels.
+ * * `samples_per_pixel` is the number of samples per pixel.
+ * * `max_depth` is the recursion limit for ray reflections.
+ * * `cam` is the camera.
+ * * `filter` is the pixel reconstruction filter.
+ * * `sampler` generates the sample values.
+ * * If `spectral` is `true`, each sample traces several wavelengths of light, with spectra
+ *   upsampled from the colours in the scene, so that materials can vary with wavelength.
+ * * `white_balance`, if given, is the colour temperature of the light, in kelvins, to be shown as
+ *   neutral in the output.
+ * * `frames` is the range of frame numbers to render.
+ * * `fps` is the number of frames per second.
+ * * `filename_pattern` is the name of the output files, with a `printf`-style placeholder for the
+ *   frame number, such as `out_%04d.png`.  The file format is determined by the extension.
+ * * If `log` is `true`, progress is reported to the standard error stream.
+ */
+#[allow(clippy::too_many_arguments)]
+pub fn run_sequence(
+    objects: &[Arc<dyn Hittable>],
+    background: Arc<dyn Background>,
+    lights: &[Arc<dyn Light>],
+    image_width: u32,
+    image_height: u32,
+    samples_per_pixel: u32,
+    max_depth: u32,
+    cam: &Camera,
+    filter: Arc<dyn Filter>,
+    sampler: &dyn Sampler,
+    spectral: bool,
+    white_balance: Option<f64>,
+    frames: RangeInclusive<u32>,
+    fps: f64,
+    filename_pattern: &str,
+    log: bool,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    assert!(!objects.is_empty());
+    assert!(fps > 0.0);
+
+    let lights: Arc<[Arc<dyn Light>]> = Arc::from(lights);
+
+    for frame in frames {
+        let filename = frame_filename(filename_pattern, frame)?;
+        if filename.exists() {
+            if log {
+                eprintln!("Skipping frame {}: “{}” exists.", frame, filename.display());
+            }
+            continue;
+        }
+
+        if log {
+            eprintln!("Rendering frame {} to “{}”...", frame, filename.display());
+        }
+
+        let time_range = f64::from(frame) / fps..(f64::from(frame) + 1.0) / fps;
+
+        let mut cam = cam.clone();
+        cam.set_time_range(time_range.clone());
+        let world = BvhNode::new_hittable(objects, time_range);
+
+        let mut pixels = render_parallel(
+            world,
+            Arc::clone(&background),
+            Arc::clone(&lights),
+            image_width,
+            image_height,
+            samples_per_pixel,
+            max_depth,
+            Arc::new(cam),
+            Arc::clone(&filter),
+            sampler,
+            spectral,
+            log,
+        )?;
+
+        if let Some(temperature) = white_balance {
+            apply_white_balance(&mut pixels, temperature);
+        }
+
+        write_image_file(&filename, &pixels, image_width, image_height)?;
+    }
+
+    if log {
+        eprintln!("Done.");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::frame_filename;
+    use std::path::PathBuf;
+
+    fn assert_filename(pattern: &str, frame: u32, expected: &str) {
+        let filename = frame_filename(pattern, frame).unwrap();
+        assert_eq!(filename, PathBuf::from(expected), "pattern “{}”", pattern);
+    }
+
+    #[test]
+    fn plain() {
+        assert_filename("frame%d.png", 7, "frame7.png");
+        assert_filename("frame%d.png", 1234, "frame1234.png");
+    }
+
+    #[test]
+    fn padded() {
+        assert_filename("frame%4d.png", 7, "frame   7.png");
+        assert_filename("frame%04d.png", 7, "frame0007.png");
+        assert_filename("frame%04d.png", 12345, "frame12345.png");
+    }
+
+    #[test]
+    fn percent() {
+        assert_filename("100%%_%d.png", 3, "100%_3.png");
+        assert_filename("%%d_%d.png", 3, "%d_3.png");
+        assert_filename("50%_%d.png", 3, "50%_3.png");
+    }
+
+    #[test]
+    fn first_conversion_only() {
+        assert_filename("%d_%d.png", 3, "3_%d.png");
+    }
+
+    #[test]
+    fn missing_placeholder() {
+        assert!(frame_filename("frame.png", 1).is_err());
+        assert!(frame_filename("frame%%d.png", 1).is_err());
+    }
+
+    #[test]
+    fn malformed_placeholder() {
+        assert!(frame_filename("frame%x.png", 1).is_err());
+        assert!(frame_filename("frame%4.png", 1).is_err());
+        assert!(frame_filename("frame%99999999999999999999999d.png", 1).is_err());
+    }
 }
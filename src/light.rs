@@ -0,0 +1,40 @@
+mod directional;
+mod ies;
+mod point;
+mod spot;
+use crate::{Colour, Quaternion, Vec3};
+pub use directional::DirectionalLight;
+pub use ies::IesProfile;
+pub use point::PointLight;
+pub use spot::SpotLight;
+use std::{fmt::Debug, sync::Arc};
+
+/**
+ * Trait for light sources that are infinitely small or infinitely far away.
+ *
+ * Rays can never hit these lights, so they are sampled explicitly: at each surface that scatters
+ * light diffusely, a shadow ray is traced towards every light.
+ */
+pub trait Light: Debug + Send + Sync {
+    /**
+     * Returns the light arriving at point `p` from the light source, or `None` if the light does
+     * not shine on `p`.  Returns the direction towards the light, as a unit vector, the distance
+     * to the light (infinite for lights infinitely far away), and the irradiance at `p` on a
+     * surface facing the light.
+     */
+    #[must_use]
+    fn illuminate(&self, p: Vec3) -> Option<(Vec3, f64, Colour)>;
+}
+
+/**
+ * Returns the fraction of a light’s intensity emitted in the direction `w` in the scene, given
+ * its optional photometric profile and the rotation of the profile into the scene.  The tilt of
+ * the luminaire is the angle its nadir is turned from −y.
+ */
+pub(crate) fn profile_intensity(profile: Option<&(Arc<IesProfile>, Quaternion)>, w: Vec3) -> f64 {
+    profile.map_or(1.0, |(profile, rotation)| {
+        let nadir = rotation.rotate(Vec3(0.0, -1.0, 0.0)).unit();
+        let tilt = (-nadir.y()).clamp(-1.0, 1.0).acos().to_degrees();
+        profile.tilt_multiplier(tilt) * profile.relative_intensity(rotation.conjugate().rotate(w))
+    })
+}
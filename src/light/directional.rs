@@ -0,0 +1,37 @@
+use super::Light;
+use crate::{Colour, Vec3};
+use std::sync::Arc;
+
+/**
+ * Type for light sources infinitely far away, shining in a single direction, like the sun.
+ */
+#[derive(Debug)]
+pub struct DirectionalLight {
+    direction: Vec3,
+    irradiance: Colour,
+}
+
+impl DirectionalLight {
+    /**
+     * Creates a directional light shining along `direction`, with irradiance `irradiance` on a
+     * surface facing the light.
+     */
+    #[must_use]
+    pub fn new(direction: Vec3, irradiance: Colour) -> Self {
+        Self {
+            direction: direction.unit(),
+            irradiance,
+        }
+    }
+
+    #[must_use]
+    pub fn new_light(direction: Vec3, irradiance: Colour) -> Arc<dyn Light> {
+        Arc::new(Self::new(direction, irradiance))
+    }
+}
+
+impl Light for DirectionalLight {
+    fn illuminate(&self, _p: Vec3) -> Option<(Vec3, f64, Colour)> {
+        Some((-self.direction, f64::INFINITY, self.irradiance))
+    }
+}
@@ -0,0 +1,445 @@
+use crate::Vec3;
+use std::{error::Error, fs, path::Path};
+
+/**
+ * Type for the measured distribution of light from a luminaire, read from an IES LM-63
+ * photometric data file.
+ *
+ * Only type C photometry, used for almost all architectural and area luminaires, is supported.
+ * In the profile’s own frame the nadir (vertical angle 0°) points along −y, and horizontal angles
+ * are measured about the y axis from +x (0°) towards −z (90°).
+ *
+ * Tilt data included in the file scales the whole distribution by the angle the luminaire is
+ * tilted from its normal orientation, taken as the angle between its nadir and −y in the scene.
+ */
+#[derive(Debug)]
+pub struct IesProfile {
+    /**
+     * Vertical angles, in ascending order, in degrees.
+     */
+    vertical_angles: Box<[f64]>,
+
+    /**
+     * Horizontal angles, in ascending order, in degrees.
+     */
+    horizontal_angles: Box<[f64]>,
+
+    /**
+     * Luminous intensities in candela, with `vertical_angles.len()` entries for each horizontal
+     * angle.
+     */
+    candela: Box<[f64]>,
+
+    max_candela: f64,
+
+    /**
+     * Tilt angles, in ascending order, in degrees, or empty if the file has no tilt data.
+     */
+    tilt_angles: Box<[f64]>,
+
+    /**
+     * Multipliers of the luminous intensity for each tilt angle.
+     */
+    tilt_multipliers: Box<[f64]>,
+}
+
+impl IesProfile {
+    /**
+     * Loads a profile from the IES file `filename`.
+     */
+    pub fn new(filename: impl AsRef<Path>) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        let filename = filename.as_ref();
+        let data = fs::read(filename)
+            .map_err(|x| format!("cannot read IES file “{}”: {}", filename.display(), x))?;
+        Self::parse(&String::from_utf8_lossy(&data))
+            .map_err(|x| format!("invalid IES file “{}”: {}", filename.display(), x).into())
+    }
+
+    /**
+     * Parses a profile from the text of an IES file.
+     */
+    pub fn parse(text: &str) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        // Skip the version and the keywords up to the TILT line.
+        let mut lines = text.lines();
+        let tilt = lines
+            .by_ref()
+            .map(str::trim)
+            .find(|line| line.starts_with("TILT="))
+            .ok_or("missing TILT line")?;
+        let mut values = lines
+            .flat_map(|line| line.split(|c: char| c.is_whitespace() || c == ','))
+            .filter(|token| !token.is_empty())
+            .map(|token| {
+                token
+                    .parse::<f64>()
+                    .map_err(|_| format!("invalid number “{}”", token))
+            });
+        let mut next = move || {
+            values
+                .next()
+                .unwrap_or_else(|| Err("unexpected end of file".into()))
+        };
+
+        let (tilt_angles, tilt_multipliers) = match &tilt["TILT=".len()..] {
+            "NONE" => (Box::default(), Box::default()),
+            "INCLUDE" => {
+                // Lamp-to-luminaire geometry, then the angles followed by their multipliers.
+                next()?;
+                let pairs = next()? as usize;
+                if pairs == 0 {
+                    return Err("no tilt angles".into());
+                }
+                let angles = (0..pairs)
+                    .map(|_| next())
+                    .collect::<Result<Box<[f64]>, _>>()?;
+                let multipliers = (0..pairs)
+                    .map(|_| next())
+                    .collect::<Result<Box<[f64]>, _>>()?;
+                if !angles.windows(2).all(|w| w[0] < w[1]) {
+                    return Err("tilt angles not in ascending order".into());
+                }
+                (angles, multipliers)
+            }
+            file => return Err(format!("unsupported tilt file “{}”", file).into()),
+        };
+
+        let _lamps = next()?;
+        let _lumens_per_lamp = next()?;
+        let multiplier = next()?;
+        let vertical_count = next()? as usize;
+        let horizontal_count = next()? as usize;
+        let photometric_type = next()?;
+        let _units = next()?;
+        for _ in 0..3 {
+            next()?; // luminous opening width, length and height
+        }
+        for _ in 0..3 {
+            next()?; // ballast factor, ballast–lamp factor and input watts
+        }
+        if photometric_type != 1.0 {
+            return Err(format!(
+                "unsupported photometric type “{}”, only type C is supported",
+                photometric_type
+            )
+            .into());
+        }
+        if vertical_count == 0 || horizontal_count == 0 {
+            return Err("no angles".into());
+        }
+
+        let vertical_angles = (0..vertical_count)
+            .map(|_| next())
+            .collect::<Result<Box<[f64]>, _>>()?;
+        let horizontal_angles = (0..horizontal_count)
+            .map(|_| next())
+            .collect::<Result<Box<[f64]>, _>>()?;
+        let candela = (0..vertical_count * horizontal_count)
+            .map(|_| next().map(|c| multiplier * c))
+            .collect::<Result<Box<[f64]>, _>>()?;
+        if !vertical_angles.windows(2).all(|w| w[0] < w[1])
+            || !horizontal_angles.windows(2).all(|w| w[0] < w[1])
+        {
+            return Err("angles not in ascending order".into());
+        }
+
+        let max_candela = candela.iter().copied().fold(0.0, f64::max);
+        Ok(Self {
+            vertical_angles,
+            horizontal_angles,
+            candela,
+            max_candela,
+            tilt_angles,
+            tilt_multipliers,
+        })
+    }
+
+    /**
+     * Returns the largest luminous intensity in the profile, in candela.
+     */
+    #[must_use]
+    pub const fn max_candela(&self) -> f64 {
+        self.max_candela
+    }
+
+    /**
+     * Returns the luminous intensity, in candela, in the direction `direction` in the profile’s
+     * frame.
+     */
+    #[must_use]
+    pub fn candela(&self, direction: Vec3) -> f64 {
+        let direction = direction.unit();
+        let vertical = (-direction.y()).clamp(-1.0, 1.0).acos().to_degrees();
+        let horizontal = (-direction.z())
+            .atan2(direction.x())
+            .to_degrees()
+            .rem_euclid(360.0);
+
+        // Profiles only covering part of the circle are symmetric about the planes at their
+        // last horizontal angle.
+        let last = self.horizontal_angles[self.horizontal_angles.len() - 1];
+        let horizontal = if self.horizontal_angles.len() == 1 {
+            self.horizontal_angles[0]
+        } else if last == 90.0 {
+            let h = if horizontal > 180.0 {
+                360.0 - horizontal
+            } else {
+                horizontal
+            };
+            if h > 90.0 {
+                180.0 - h
+            } else {
+                h
+            }
+        } else if last == 180.0 && horizontal > 180.0 {
+            360.0 - horizontal
+        } else {
+            horizontal
+        };
+
+        let (v0, v1, tv) = match bracket(&self.vertical_angles, vertical, false) {
+            Some(bracket) => bracket,
+            None => return 0.0,
+        };
+        let (h0, h1, th) = match bracket(&self.horizontal_angles, horizontal, true) {
+            Some(bracket) => bracket,
+            None => return 0.0,
+        };
+        let n = self.vertical_angles.len();
+        let at = |h: usize, v: usize| self.candela[h * n + v];
+        let c0 = (1.0 - tv) * at(h0, v0) + tv * at(h0, v1);
+        let c1 = (1.0 - tv) * at(h1, v0) + tv * at(h1, v1);
+        (1.0 - th) * c0 + th * c1
+    }
+
+    /**
+     * Returns the factor scaling the luminous intensity when the luminaire is tilted by `tilt`
+     * degrees from its normal orientation.  Tilts beyond the range of the tilt data use the
+     * nearest multiplier.
+     */
+    #[must_use]
+    pub fn tilt_multiplier(&self, tilt: f64) -> f64 {
+        if self.tilt_angles.is_empty() {
+            return 1.0;
+        }
+
+        let last = self.tilt_angles.len() - 1;
+        let tilt = tilt.clamp(self.tilt_angles[0], self.tilt_angles[last]);
+        match bracket(&self.tilt_angles, tilt, false) {
+            Some((i0, i1, t)) => {
+                (1.0 - t) * self.tilt_multipliers[i0] + t * self.tilt_multipliers[i1]
+            }
+            None => 1.0,
+        }
+    }
+
+    /**
+     * Returns the luminous intensity in the direction `direction` in the profile’s frame, as a
+     * fraction of the largest intensity in the profile.
+     */
+    #[must_use]
+    pub fn relative_intensity(&self, direction: Vec3) -> f64 {
+        if self.max_candela > 0.0 {
+            self.candela(direction) / self.max_candela
+        } else {
+            0.0
+        }
+    }
+}
+
+/**
+ * Finds the entries of the ascending `angles` either side of `angle`, and the fraction of the way
+ * from the first to the second.  Returns `None` if `angle` is out of range, unless `wrap` is
+ * `true`, in which case the angles are treated as periodic with period 360°.
+ */
+fn bracket(angles: &[f64], angle: f64, wrap: bool) -> Option<(usize, usize, f64)> {
+    let last = angles.len() - 1;
+    if angles.len() == 1 {
+        return if wrap || angle == angles[0] {
+            Some((0, 0, 0.0))
+        } else {
+            None
+        };
+    }
+    if angle < angles[0] || angle > angles[last] {
+        if !wrap {
+            return None;
+        }
+        let start = angles[last];
+        let end = angles[0] + 360.0;
+        if end <= start {
+            return Some((last, last, 0.0));
+        }
+        let angle = if angle < angles[0] {
+            angle + 360.0
+        } else {
+            angle
+        };
+        return Some((last, 0, (angle - start) / (end - start)));
+    }
+
+    let i = angles.partition_point(|&a| a <= angle).clamp(1, last);
+    let t = (angle - angles[i - 1]) / (angles[i] - angles[i - 1]);
+    Some((i - 1, i, t.clamp(0.0, 1.0)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IesProfile;
+    use crate::Vec3;
+    use std::f64::consts::FRAC_1_SQRT_2;
+
+    /**
+     * Parses a profile with the given tilt section, multiplier, angles and candela values.
+     */
+    fn profile(
+        tilt: &str,
+        multiplier: f64,
+        vertical: &[f64],
+        horizontal: &[f64],
+        candela: &[f64],
+    ) -> IesProfile {
+        let join = |values: &[f64]| {
+            values
+                .iter()
+                .map(f64::to_string)
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        let text = format!(
+            "IESNA:LM-63-2002\n[TEST] profile\nTILT={}\n\
+             1 1000 {} {} {} 1 2 0 0 0\n1 1 100\n{}\n{}\n{}\n",
+            tilt,
+            multiplier,
+            vertical.len(),
+            horizontal.len(),
+            join(vertical),
+            join(horizontal),
+            join(candela)
+        );
+        IesProfile::parse(&text).unwrap()
+    }
+
+    /**
+     * Returns the direction at `vertical` degrees from the nadir and `horizontal` degrees about
+     * the vertical axis.
+     */
+    fn direction(vertical: f64, horizontal: f64) -> Vec3 {
+        let (sin_v, cos_v) = vertical.to_radians().sin_cos();
+        let (sin_h, cos_h) = horizontal.to_radians().sin_cos();
+        Vec3(sin_v * cos_h, -cos_v, -sin_v * sin_h)
+    }
+
+    fn assert_candela(profile: &IesProfile, vertical: f64, horizontal: f64, expected: f64) {
+        let candela = profile.candela(direction(vertical, horizontal));
+        assert!(
+            (candela - expected).abs() < 1e-9,
+            "candela at ({}°, {}°) is {}, expected {}",
+            vertical,
+            horizontal,
+            candela,
+            expected
+        );
+    }
+
+    #[test]
+    fn rotationally_symmetric() {
+        let p = profile("NONE", 2.0, &[0.0, 90.0, 180.0], &[0.0], &[50.0, 25.0, 0.0]);
+        assert!((p.max_candela() - 100.0).abs() < 1e-9);
+        assert_candela(&p, 0.0, 0.0, 100.0);
+        assert_candela(&p, 45.0, 0.0, 75.0);
+        for &h in &[0.0, 90.0, 137.0, 270.0] {
+            assert_candela(&p, 90.0, h, 50.0);
+        }
+        assert_candela(&p, 180.0, 0.0, 0.0);
+    }
+
+    #[test]
+    fn quadrant_symmetric() {
+        let p = profile(
+            "NONE",
+            1.0,
+            &[0.0, 90.0],
+            &[0.0, 90.0],
+            &[100.0, 80.0, 100.0, 20.0],
+        );
+        assert_candela(&p, 90.0, 0.0, 80.0);
+        assert_candela(&p, 90.0, 45.0, 50.0);
+        assert_candela(&p, 90.0, 90.0, 20.0);
+        assert_candela(&p, 90.0, 180.0, 80.0);
+        assert_candela(&p, 90.0, 270.0, 20.0);
+        assert_candela(&p, 90.0, 300.0, 40.0);
+    }
+
+    #[test]
+    fn bilaterally_symmetric() {
+        let p = profile(
+            "NONE",
+            1.0,
+            &[0.0, 90.0],
+            &[0.0, 90.0, 180.0],
+            &[10.0, 60.0, 10.0, 40.0, 10.0, 20.0],
+        );
+        assert_candela(&p, 90.0, 0.0, 60.0);
+        assert_candela(&p, 90.0, 135.0, 30.0);
+        assert_candela(&p, 90.0, 180.0, 20.0);
+        assert_candela(&p, 90.0, 225.0, 30.0);
+        assert_candela(&p, 90.0, 270.0, 40.0);
+        assert_candela(&p, 45.0, 270.0, 25.0);
+    }
+
+    #[test]
+    fn full_circle() {
+        let p = profile(
+            "NONE",
+            1.0,
+            &[0.0, 90.0],
+            &[0.0, 90.0, 180.0, 270.0, 360.0],
+            &[0.0, 10.0, 0.0, 20.0, 0.0, 30.0, 0.0, 40.0, 0.0, 10.0],
+        );
+        assert_candela(&p, 90.0, 90.0, 20.0);
+        assert_candela(&p, 90.0, 180.0, 30.0);
+        assert_candela(&p, 90.0, 270.0, 40.0);
+        assert_candela(&p, 90.0, 315.0, 25.0);
+        assert_candela(&p, 90.0, 45.0, 15.0);
+    }
+
+    #[test]
+    fn tilt_none() {
+        let p = profile("NONE", 1.0, &[0.0, 90.0], &[0.0], &[100.0, 40.0]);
+        for &tilt in &[0.0, 30.0, 90.0] {
+            assert!((p.tilt_multiplier(tilt) - 1.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn tilt_include() {
+        let p = profile(
+            "INCLUDE\n1\n3\n0 45 90\n1 0.9 0.8",
+            1.0,
+            &[0.0, 90.0],
+            &[0.0],
+            &[100.0, 40.0],
+        );
+        assert_candela(&p, 0.0, 0.0, 100.0);
+        assert_candela(&p, 90.0, 0.0, 40.0);
+        let d = Vec3(FRAC_1_SQRT_2, -FRAC_1_SQRT_2, 0.0);
+        assert!((p.candela(d) - 70.0).abs() < 1e-9);
+
+        for &(tilt, expected) in &[
+            (0.0, 1.0),
+            (22.5, 0.95),
+            (45.0, 0.9),
+            (90.0, 0.8),
+            (120.0, 0.8),
+        ] {
+            let multiplier = p.tilt_multiplier(tilt);
+            assert!(
+                (multiplier - expected).abs() < 1e-9,
+                "tilt multiplier at {}° is {}, expected {}",
+                tilt,
+                multiplier,
+                expected
+            );
+        }
+    }
+}
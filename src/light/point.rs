@@ -0,0 +1,65 @@
+use super::{profile_intensity, IesProfile, Light};
+use crate::{Colour, Quaternion, Vec3};
+use std::sync::Arc;
+
+/**
+ * Type for light sources emitting equally in all directions from a point.
+ */
+#[derive(Debug)]
+pub struct PointLight {
+    position: Vec3,
+    intensity: Colour,
+    profile: Option<(Arc<IesProfile>, Quaternion)>,
+}
+
+impl PointLight {
+    /**
+     * Creates a point light at `position`, with radiant intensity `intensity` (power per unit
+     * solid angle).  The irradiance falls off with the square of the distance.
+     */
+    #[must_use]
+    pub fn new(position: Vec3, intensity: Colour) -> Self {
+        Self {
+            position,
+            intensity,
+            profile: None,
+        }
+    }
+
+    #[must_use]
+    pub fn new_light(position: Vec3, intensity: Colour) -> Arc<dyn Light> {
+        Arc::new(Self::new(position, intensity))
+    }
+
+    /**
+     * Modulates the emission by the luminous intensity distribution `profile`, relative to its
+     * brightest direction.  `rotation` turns the profile’s frame, whose nadir is −y, into the
+     * scene.
+     */
+    pub fn set_profile(&mut self, profile: Arc<IesProfile>, rotation: Quaternion) {
+        self.profile = Some((profile, rotation));
+    }
+}
+
+impl Light for PointLight {
+    fn illuminate(&self, p: Vec3) -> Option<(Vec3, f64, Colour)> {
+        let offset = self.position - p;
+        let distance_squared = offset.dot(offset);
+        if distance_squared <= 0.0 {
+            return None;
+        }
+
+        let distance = distance_squared.sqrt();
+        let direction = offset / distance;
+        let profile = profile_intensity(self.profile.as_ref(), -direction);
+        if profile <= 0.0 {
+            return None;
+        }
+
+        Some((
+            direction,
+            distance,
+            (profile / distance_squared) * self.intensity,
+        ))
+    }
+}
@@ -0,0 +1,125 @@
+use super::{profile_intensity, IesProfile, Light};
+use crate::{Colour, Quaternion, Vec3};
+use std::sync::Arc;
+
+/**
+ * Type for light sources emitting from a point in a cone of directions.
+ */
+#[derive(Debug)]
+pub struct SpotLight {
+    position: Vec3,
+    direction: Vec3,
+    intensity: Colour,
+    cos_inner_angle: f64,
+    cos_outer_angle: f64,
+    falloff: f64,
+    profile: Option<(Arc<IesProfile>, Quaternion)>,
+}
+
+impl SpotLight {
+    /**
+     * Creates a spotlight.
+     *
+     * # Parameters
+     *
+     * * `position` is the position of the light.
+     * * `target` is a point on the axis of the cone.
+     * * `intensity` is the radiant intensity (power per unit solid angle) within the inner cone.
+     *   The irradiance falls off with the square of the distance.
+     * * `inner_angle` and `outer_angle` are the angles from the axis, in degrees, of the fully lit
+     *   inner cone and of the edge of the beam.
+     * * `falloff` is the exponent of the fade between the inner and outer cones: 1 fades linearly
+     *   in the cosine of the angle; larger values give a softer edge.
+     */
+    #[must_use]
+    pub fn new(
+        position: Vec3,
+        target: Vec3,
+        intensity: Colour,
+        inner_angle: f64,
+        outer_angle: f64,
+        falloff: f64,
+    ) -> Self {
+        assert!(inner_angle >= 0.0);
+        assert!(inner_angle <= outer_angle);
+        assert!(outer_angle <= 180.0);
+        assert!(falloff > 0.0);
+        Self {
+            position,
+            direction: (target - position).unit(),
+            intensity,
+            cos_inner_angle: inner_angle.to_radians().cos(),
+            cos_outer_angle: outer_angle.to_radians().cos(),
+            falloff,
+            profile: None,
+        }
+    }
+
+    #[must_use]
+    pub fn new_light(
+        position: Vec3,
+        target: Vec3,
+        intensity: Colour,
+        inner_angle: f64,
+        outer_angle: f64,
+        falloff: f64,
+    ) -> Arc<dyn Light> {
+        Arc::new(Self::new(
+            position,
+            target,
+            intensity,
+            inner_angle,
+            outer_angle,
+            falloff,
+        ))
+    }
+
+    /**
+     * Modulates the emission by the luminous intensity distribution `profile`, relative to its
+     * brightest direction.  `rotation` turns the profile’s frame, whose nadir is −y, into the
+     * scene.
+     */
+    pub fn set_profile(&mut self, profile: Arc<IesProfile>, rotation: Quaternion) {
+        self.profile = Some((profile, rotation));
+    }
+
+    /**
+     * Returns the fraction of the full intensity emitted in the unit vector direction `w`.
+     */
+    #[must_use]
+    fn cone(&self, w: Vec3) -> f64 {
+        let cos_theta = w.dot(self.direction);
+        if cos_theta >= self.cos_inner_angle {
+            1.0
+        } else if cos_theta <= self.cos_outer_angle {
+            0.0
+        } else {
+            let t =
+                (cos_theta - self.cos_outer_angle) / (self.cos_inner_angle - self.cos_outer_angle);
+            t.powf(self.falloff)
+        }
+    }
+}
+
+impl Light for SpotLight {
+    fn illuminate(&self, p: Vec3) -> Option<(Vec3, f64, Colour)> {
+        let offset = self.position - p;
+        let distance_squared = offset.dot(offset);
+        if distance_squared <= 0.0 {
+            return None;
+        }
+
+        let distance = distance_squared.sqrt();
+        let direction = offset / distance;
+        let cone = self.cone(-direction) * profile_intensity(self.profile.as_ref(), -direction);
+        if cone <= 0.0 {
+            return None;
+        }
+
+        Some((
+            direction,
+            distance,
+            (cone / distance_squared) * self.intensity,
+        ))
+    }
+}
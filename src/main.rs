@@ -1,7 +1,13 @@
 use rand::{distributions::Uniform, prelude::*};
 use rays::{
-    Block, BvhNode, Camera, Chequer, Colour, ConstantMedium, Dielectric, DiffuseLight, Hittable,
-    Image, Lambertian2, Metal, MovingSphere, Noise, RotateY, Sphere, Translate, Vec3, XyRect,
+    AddMaterial, Aperture, Background, Block, BlueNoiseSampler, BoxFilter, BvhNode, Camera,
+    CameraKey, CameraRig, Chequer, Coated, Colour, Conductor, ConductorPreset, ConstantMedium,
+    Dielectric, DiffuseLight, DirectionalLight, EnvironmentMap, Filter, FocusTarget,
+    GaussianFilter, GradientBackground, HaltonSampler, Hittable, IesProfile, Image,
+    IndependentSampler, Interpolation, Lambertian2, LanczosFilter, Light, Material, Metal,
+    MitchellFilter, Mix, MotionKey, Moving, MovingSphere, Noise, OrenNayar, PointLight, Principled,
+    Quaternion, RefractiveIndex, RotateY, RoughDielectric, Sampler, Sky, SobolSampler, Sphere,
+    SpotLight, Stereo, StereoFormat, StratifiedSampler, TentFilter, Translate, Vec3, XyRect,
     XzRect, YzRect,
 };
 use std::{
@@ -10,13 +16,37 @@ use std::{
     ffi::{OsStr, OsString},
     fs::File,
     io::{self, prelude::*, BufWriter},
+    ops::RangeInclusive,
     path::Path,
     process,
     sync::Arc,
 };
 
+/**
+ * Apparent diameter of the sun for `--sky`, in degrees.
+ */
+const SUN_ANGULAR_DIAMETER: f64 = 0.53;
+
+/**
+ * Scale from the sky’s luminance, in kcd/m², to the brightness of the scenes.
+ */
+const SKY_INTENSITY: f64 = 0.05;
+
+/**
+ * Photometric profile of a downlight with a 120° beam, in IES LM-63 format.
+ */
+const DOWNLIGHT_PROFILE: &str = "IESNA:LM-63-2002
+[TEST] Downlight
+TILT=NONE
+1 1000 1 7 1 1 2 0.1 0.1 0
+1 1 20
+0 15 30 45 60 75 90
+0
+1000 950 800 500 150 20 0
+";
+
 #[must_use]
-fn random_scene() -> Arc<dyn Hittable> {
+fn random_scene() -> Vec<Arc<dyn Hittable>> {
     let mut rand_eng = thread_rng();
 
     let mut world = Vec::new();
@@ -82,11 +112,11 @@ fn random_scene() -> Arc<dyn Hittable> {
     let material3 = Metal::new_material(Colour(0.7, 0.6, 0.5), 0.0);
     world.push(Sphere::new_hittable(Vec3(4.0, 1.0, 0.0), 1.0, material3));
 
-    Arc::new(world)
+    world
 }
 
 #[must_use]
-fn two_spheres() -> Arc<dyn Hittable> {
+fn two_spheres() -> Vec<Arc<dyn Hittable>> {
     let chequer = Chequer::new_texture(
         Vec3(10.0, 10.0, 10.0),
         Colour(0.2, 0.3, 0.1),
@@ -106,11 +136,11 @@ fn two_spheres() -> Arc<dyn Hittable> {
         ),
     ];
 
-    Arc::new(objects)
+    objects
 }
 
 #[must_use]
-fn two_perlin_spheres() -> Arc<dyn Hittable> {
+fn two_perlin_spheres() -> Vec<Arc<dyn Hittable>> {
     let pertext = Noise::new_texture(4.0);
     let objects = vec![
         Sphere::new_hittable(
@@ -121,20 +151,20 @@ fn two_perlin_spheres() -> Arc<dyn Hittable> {
         Sphere::new_hittable(Vec3(0.0, 2.0, 0.0), 2.0, Lambertian2::new_material(pertext)),
     ];
 
-    Arc::new(objects)
+    objects
 }
 
-fn earth() -> Result<Arc<dyn Hittable>, Box<dyn Error + Send + Sync>> {
+fn earth() -> Result<Vec<Arc<dyn Hittable>>, Box<dyn Error + Send + Sync>> {
     let earth_texture = Image::new_texture("earthmap.jpg")?;
     let earth_surface = Lambertian2::new_material(earth_texture);
     let globe = Sphere::new_hittable(Vec3(0.0, 0.0, 0.0), 2.0, earth_surface);
-    Ok(globe)
+    Ok(vec![globe])
 }
 
 #[must_use]
-fn simple_light() -> Arc<dyn Hittable> {
+fn simple_light() -> Vec<Arc<dyn Hittable>> {
     let pertext = Noise::new_texture(4.0);
-    let difflight = DiffuseLight::new_material(Colour(4.0, 4.0, 4.0));
+    let difflight = DiffuseLight::new_material_with_intensity(Colour(1.0, 1.0, 1.0), 4.0, true);
 
     let objects = vec![
         Sphere::new_hittable(
@@ -146,15 +176,169 @@ fn simple_light() -> Arc<dyn Hittable> {
         XyRect::new_hittable(3.0..5.0, 1.0..3.0, -2.0, difflight),
     ];
 
-    Arc::new(objects)
+    objects
+}
+
+#[must_use]
+fn moving_blocks() -> Vec<Arc<dyn Hittable>> {
+    let chequer = Chequer::new_texture(
+        Vec3(4.0, 4.0, 4.0),
+        Colour(0.2, 0.3, 0.1),
+        Colour(0.9, 0.9, 0.9),
+    );
+    let red = Lambertian2::new_material(Colour(0.65, 0.05, 0.05));
+    let metal = Metal::new_material(Colour(0.8, 0.8, 0.9), 0.05);
+
+    // A block sliding, turning and growing, and a ball thrown in an arc.
+    let block = Block::new_hittable(Vec3(-0.5, -0.5, -0.5), Vec3(0.5, 0.5, 0.5), red);
+    let block_keys = vec![
+        MotionKey::new(
+            0.0,
+            Vec3(-2.0, 0.5, 0.0),
+            Quaternion::new_identity(),
+            Vec3(1.0, 1.0, 1.0),
+        ),
+        MotionKey::new(
+            1.0,
+            Vec3(-1.0, 0.75, 0.0),
+            Quaternion::new_rotation(Vec3(0.0, 1.0, 0.0), 60.0),
+            Vec3(1.5, 1.5, 1.5),
+        ),
+    ];
+
+    let ball = Sphere::new_hittable(Vec3(0.0, 0.0, 0.0), 0.5, metal);
+    let ball_keys = vec![
+        MotionKey::new(
+            0.0,
+            Vec3(1.0, 0.5, 0.0),
+            Quaternion::new_identity(),
+            Vec3(1.0, 1.0, 1.0),
+        ),
+        MotionKey::new(
+            0.5,
+            Vec3(2.0, 1.5, 0.0),
+            Quaternion::new_identity(),
+            Vec3(1.0, 1.0, 1.0),
+        ),
+        MotionKey::new(
+            1.0,
+            Vec3(3.0, 0.5, 0.0),
+            Quaternion::new_identity(),
+            Vec3(1.0, 1.0, 1.0),
+        ),
+    ];
+
+    vec![
+        Sphere::new_hittable(
+            Vec3(0.0, -1000.0, 0.0),
+            1000.0,
+            Lambertian2::new_material(chequer),
+        ),
+        Moving::new_hittable(block, block_keys),
+        Moving::new_hittable(ball, ball_keys),
+    ]
+}
+
+#[must_use]
+fn lit_spheres() -> Vec<Arc<dyn Hittable>> {
+    let ground = Lambertian2::new_material(Colour(0.5, 0.5, 0.5));
+    let red = Lambertian2::new_material(Colour(0.7, 0.1, 0.1));
+    let metal = Metal::new_material(Colour(0.8, 0.8, 0.8), 0.1);
+    let white = Lambertian2::new_material(Colour(0.73, 0.73, 0.73));
+
+    vec![
+        Sphere::new_hittable(Vec3(0.0, -1000.0, 0.0), 1000.0, ground),
+        Sphere::new_hittable(Vec3(-2.5, 1.0, 0.0), 1.0, red),
+        Sphere::new_hittable(Vec3(0.0, 1.0, 0.0), 1.0, metal),
+        Sphere::new_hittable(Vec3(2.5, 1.0, 0.0), 1.0, white),
+    ]
+}
+
+/**
+ * Returns the spheres of `lit_spheres`, with a ceiling panel over the left one emitting light with
+ * the photometric profile `profile`.
+ */
+#[must_use]
+fn photometric_spheres(profile: &Arc<IesProfile>) -> Vec<Arc<dyn Hittable>> {
+    let mut panel = DiffuseLight::new_with_intensity(Colour(1.0, 0.9, 0.8), 4.0, true);
+    panel.set_profile(profile.clone(), Quaternion::new_identity());
+
+    let mut objects = lit_spheres();
+    objects.push(XzRect::new_hittable(
+        -3.0..-2.0,
+        -0.5..0.5,
+        4.0,
+        Arc::new(panel),
+    ));
+    objects
+}
+
+/**
+ * Returns a chequered floor with a row of spheres, of `materials` from left to right.
+ */
+#[must_use]
+fn sphere_row(materials: Vec<Arc<dyn Material>>) -> Vec<Arc<dyn Hittable>> {
+    const SPACING: f64 = 2.5;
+
+    let chequer = Chequer::new_texture(
+        Vec3(4.0, 4.0, 4.0),
+        Colour(0.05, 0.05, 0.05),
+        Colour(0.9, 0.9, 0.9),
+    );
+    let mut objects = vec![Sphere::new_hittable(
+        Vec3(0.0, -1000.0, 0.0),
+        1000.0,
+        Lambertian2::new_material(chequer),
+    )];
+
+    let first = -0.5 * SPACING * (materials.len() as f64 - 1.0);
+    for (i, material) in materials.into_iter().enumerate() {
+        let centre = Vec3(first + SPACING * i as f64, 1.0, 0.0);
+        objects.push(Sphere::new_hittable(centre, 1.0, material));
+    }
+
+    objects
+}
+
+#[must_use]
+fn dispersive_spheres() -> Vec<Arc<dyn Hittable>> {
+    let chequer = Chequer::new_texture(
+        Vec3(4.0, 4.0, 4.0),
+        Colour(0.05, 0.05, 0.05),
+        Colour(0.9, 0.9, 0.9),
+    );
+    let black = Colour(0.0, 0.0, 0.0);
+
+    vec![
+        Sphere::new_hittable(
+            Vec3(0.0, -1000.0, 0.0),
+            1000.0,
+            Lambertian2::new_material(chequer),
+        ),
+        Sphere::new_hittable(
+            Vec3(-2.5, 1.0, 0.0),
+            1.0,
+            Dielectric::new_material_dispersive(RefractiveIndex::BK7, black),
+        ),
+        Sphere::new_hittable(
+            Vec3(0.0, 1.0, 0.0),
+            1.0,
+            Dielectric::new_material_dispersive(RefractiveIndex::SF11, black),
+        ),
+        Sphere::new_hittable(
+            Vec3(2.5, 1.0, 0.0),
+            1.0,
+            Dielectric::new_material_dispersive(RefractiveIndex::DIAMOND, black),
+        ),
+    ]
 }
 
 #[must_use]
-fn cornell_box() -> Arc<dyn Hittable> {
+fn cornell_box() -> Vec<Arc<dyn Hittable>> {
     let red = Lambertian2::new_material(Colour(0.65, 0.05, 0.05));
     let white = Lambertian2::new_material(Colour(0.73, 0.73, 0.73));
     let green = Lambertian2::new_material(Colour(0.12, 0.45, 0.15));
-    let light = DiffuseLight::new_material(Colour(15.0, 15.0, 15.0));
+    let light = DiffuseLight::new_material_with_intensity(Colour(1.0, 1.0, 1.0), 15.0, true);
 
     let mut box1 = Block::new_hittable(
         Vec3(0.0, 0.0, 0.0),
@@ -183,15 +367,15 @@ fn cornell_box() -> Arc<dyn Hittable> {
         box2,
     ];
 
-    Arc::new(objects)
+    objects
 }
 
 #[must_use]
-fn cornell_smoke() -> Arc<dyn Hittable> {
+fn cornell_smoke() -> Vec<Arc<dyn Hittable>> {
     let red = Lambertian2::new_material(Colour(0.65, 0.05, 0.05));
     let white = Lambertian2::new_material(Colour(0.73, 0.73, 0.73));
     let green = Lambertian2::new_material(Colour(0.12, 0.45, 0.15));
-    let light = DiffuseLight::new_material(Colour(7.0, 7.0, 7.0));
+    let light = DiffuseLight::new_material_with_intensity(Colour(1.0, 1.0, 1.0), 7.0, true);
 
     let mut box1 = Block::new_hittable(
         Vec3(0.0, 0.0, 0.0),
@@ -222,10 +406,10 @@ fn cornell_smoke() -> Arc<dyn Hittable> {
         box2,
     ];
 
-    Arc::new(objects)
+    objects
 }
 
-fn final_scene() -> Result<Arc<dyn Hittable>, Box<dyn Error + Send + Sync>> {
+fn final_scene() -> Result<Vec<Arc<dyn Hittable>>, Box<dyn Error + Send + Sync>> {
     let mut rand_eng = thread_rng();
 
     let mut boxes1 = Vec::new();
@@ -255,7 +439,7 @@ fn final_scene() -> Result<Arc<dyn Hittable>, Box<dyn Error + Send + Sync>> {
 
     let mut objects = vec![BvhNode::new_hittable(&boxes1, 0.0..1.0)];
 
-    let light = DiffuseLight::new_material(Colour(7.0, 7.0, 7.0));
+    let light = DiffuseLight::new_material_with_intensity(Colour(1.0, 1.0, 1.0), 7.0, true);
     objects.push(XzRect::new_hittable(
         123.0..423.0,
         147.0..412.0,
@@ -328,13 +512,70 @@ fn final_scene() -> Result<Arc<dyn Hittable>, Box<dyn Error + Send + Sync>> {
         Vec3(-100.0, 270.0, 395.0),
     ));
 
-    Ok(Arc::new(objects))
+    Ok(objects)
+}
+
+/**
+ * Destination for rendered images.
+ */
+enum Output<'a> {
+    /**
+     * A single image, written to a stream.
+     */
+    Stream(&'a mut dyn Write),
+
+    /**
+     * A sequence of animation frames, each written to a file named by a pattern.
+     */
+    Sequence {
+        frames: RangeInclusive<u32>,
+        fps: f64,
+        filename_pattern: &'a str,
+    },
 }
 
 /**
- * Builds and renders a scene.
+ * Type of camera a scene is viewed through.
  */
-fn render(scene: u32, output: &mut dyn Write) -> Result<(), Box<dyn Error + Send + Sync>> {
+enum CameraType {
+    /**
+     * A thin lens camera, with the scene’s field of view and aperture diameter.
+     */
+    ThinLens,
+
+    /**
+     * A stereoscopic thin lens camera.
+     */
+    Stereo(Stereo),
+
+    /**
+     * A camera modelled on a physical camera, whose field of view and aperture diameter follow
+     * from its sensor and lens instead.  Dimensions are in millimetres.
+     */
+    Physical {
+        sensor_width: f64,
+        sensor_height: f64,
+        focal_length: f64,
+        f_number: f64,
+        mm_per_unit: f64,
+        aperture: Aperture,
+    },
+}
+
+/**
+ * Builds and renders a scene.  If `environment` is given, it replaces the scene’s background.  If
+ * `spectral` is `true`, or the scene needs it, the scene is rendered spectrally.  If
+ * `white_balance` is given, light of that colour temperature appears neutral.
+ */
+fn render(
+    scene: u32,
+    filter: Arc<dyn Filter>,
+    sampler: &dyn Sampler,
+    environment: Option<Arc<dyn Background>>,
+    mut spectral: bool,
+    white_balance: Option<f64>,
+    output: Output,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
     // Scene parameters.
     let (
         world,
@@ -345,7 +586,7 @@ fn render(scene: u32, output: &mut dyn Write) -> Result<(), Box<dyn Error + Send
         vfov,
         aspect_ratio,
         aperture,
-        dist_to_focus,
+        focus,
         time0,
         time1,
         image_width,
@@ -353,6 +594,9 @@ fn render(scene: u32, output: &mut dyn Write) -> Result<(), Box<dyn Error + Send
         samples_per_pixel,
         max_depth,
     );
+    let mut lights: Vec<Arc<dyn Light>> = Vec::new();
+    let mut camera_type = CameraType::ThinLens;
+    let mut rig = None;
 
     match scene {
         1 => {
@@ -365,7 +609,7 @@ fn render(scene: u32, output: &mut dyn Write) -> Result<(), Box<dyn Error + Send
 
             // World.
             world = random_scene();
-            background = Colour(0.7, 0.8, 1.0);
+            background = Colour(0.7, 0.8, 1.0).into();
 
             // Camera.
             lookfrom = Vec3(13.0, 2.0, 3.0);
@@ -374,7 +618,7 @@ fn render(scene: u32, output: &mut dyn Write) -> Result<(), Box<dyn Error + Send
             vfov = 20.0;
             aspect_ratio = f64::from(image_width) / f64::from(image_height);
             aperture = 0.1;
-            dist_to_focus = 10.0;
+            focus = FocusTarget::Distance(10.0);
             time0 = 0.0;
             time1 = 1.0;
         }
@@ -389,7 +633,7 @@ fn render(scene: u32, output: &mut dyn Write) -> Result<(), Box<dyn Error + Send
 
             // World.
             world = two_spheres();
-            background = Colour(0.7, 0.8, 1.0);
+            background = Colour(0.7, 0.8, 1.0).into();
 
             // Camera.
             lookfrom = Vec3(13.0, 2.0, 3.0);
@@ -398,7 +642,7 @@ fn render(scene: u32, output: &mut dyn Write) -> Result<(), Box<dyn Error + Send
             vfov = 20.0;
             aspect_ratio = f64::from(image_width) / f64::from(image_height);
             aperture = 0.0;
-            dist_to_focus = 10.0;
+            focus = FocusTarget::Point(lookat);
             time0 = 0.0;
             time1 = 1.0;
         }
@@ -413,7 +657,7 @@ fn render(scene: u32, output: &mut dyn Write) -> Result<(), Box<dyn Error + Send
 
             // World.
             world = two_perlin_spheres();
-            background = Colour(0.7, 0.8, 1.0);
+            background = Colour(0.7, 0.8, 1.0).into();
 
             // Camera.
             lookfrom = Vec3(13.0, 2.0, 3.0);
@@ -422,7 +666,7 @@ fn render(scene: u32, output: &mut dyn Write) -> Result<(), Box<dyn Error + Send
             vfov = 20.0;
             aspect_ratio = f64::from(image_width) / f64::from(image_height);
             aperture = 0.0;
-            dist_to_focus = 10.0;
+            focus = FocusTarget::Point(lookat);
             time0 = 0.0;
             time1 = 1.0;
         }
@@ -437,7 +681,7 @@ fn render(scene: u32, output: &mut dyn Write) -> Result<(), Box<dyn Error + Send
 
             // World.
             world = earth()?;
-            background = Colour(0.7, 0.8, 1.0);
+            background = Colour(0.7, 0.8, 1.0).into();
 
             // Camera.
             lookfrom = Vec3(13.0, 2.0, 3.0);
@@ -446,7 +690,7 @@ fn render(scene: u32, output: &mut dyn Write) -> Result<(), Box<dyn Error + Send
             vfov = 20.0;
             aspect_ratio = f64::from(image_width) / f64::from(image_height);
             aperture = 0.0;
-            dist_to_focus = 10.0;
+            focus = FocusTarget::Point(lookat);
             time0 = 0.0;
             time1 = 1.0;
         }
@@ -461,7 +705,7 @@ fn render(scene: u32, output: &mut dyn Write) -> Result<(), Box<dyn Error + Send
 
             // World.
             world = simple_light();
-            background = Colour(0.0, 0.0, 0.0);
+            background = Colour(0.0, 0.0, 0.0).into();
 
             // Camera.
             lookfrom = Vec3(26.0, 3.0, 6.0);
@@ -470,7 +714,7 @@ fn render(scene: u32, output: &mut dyn Write) -> Result<(), Box<dyn Error + Send
             vfov = 20.0;
             aspect_ratio = f64::from(image_width) / f64::from(image_height);
             aperture = 0.0;
-            dist_to_focus = 10.0;
+            focus = FocusTarget::Point(lookat);
             time0 = 0.0;
             time1 = 1.0;
         }
@@ -485,7 +729,7 @@ fn render(scene: u32, output: &mut dyn Write) -> Result<(), Box<dyn Error + Send
 
             // World.
             world = cornell_box();
-            background = Colour(0.0, 0.0, 0.0);
+            background = Colour(0.0, 0.0, 0.0).into();
 
             // Camera.
             lookfrom = Vec3(278.0, 278.0, -800.0);
@@ -494,7 +738,7 @@ fn render(scene: u32, output: &mut dyn Write) -> Result<(), Box<dyn Error + Send
             vfov = 40.0;
             aspect_ratio = f64::from(image_width) / f64::from(image_height);
             aperture = 0.0;
-            dist_to_focus = 10.0;
+            focus = FocusTarget::Point(lookat);
             time0 = 0.0;
             time1 = 1.0;
         }
@@ -509,7 +753,7 @@ fn render(scene: u32, output: &mut dyn Write) -> Result<(), Box<dyn Error + Send
 
             // World.
             world = cornell_smoke();
-            background = Colour(0.0, 0.0, 0.0);
+            background = Colour(0.0, 0.0, 0.0).into();
 
             // Camera.
             lookfrom = Vec3(278.0, 278.0, -800.0);
@@ -518,7 +762,7 @@ fn render(scene: u32, output: &mut dyn Write) -> Result<(), Box<dyn Error + Send
             vfov = 40.0;
             aspect_ratio = f64::from(image_width) / f64::from(image_height);
             aperture = 0.0;
-            dist_to_focus = 10.0;
+            focus = FocusTarget::Point(lookat);
             time0 = 0.0;
             time1 = 1.0;
         }
@@ -533,7 +777,7 @@ fn render(scene: u32, output: &mut dyn Write) -> Result<(), Box<dyn Error + Send
 
             // World.
             world = final_scene()?;
-            background = Colour(0.0, 0.0, 0.0);
+            background = Colour(0.0, 0.0, 0.0).into();
 
             // Camera.
             lookfrom = Vec3(478.0, 278.0, -600.0);
@@ -542,7 +786,450 @@ fn render(scene: u32, output: &mut dyn Write) -> Result<(), Box<dyn Error + Send
             vfov = 40.0;
             aspect_ratio = f64::from(image_width) / f64::from(image_height);
             aperture = 0.0;
-            dist_to_focus = 10.0;
+            focus = FocusTarget::Point(lookat);
+            time0 = 0.0;
+            time1 = 1.0;
+        }
+
+        9 => {
+            // Image, with the views from the two eyes side by side.
+            let image_aspect_ratio = 8.0 / 3.0;
+            image_width = 800;
+            image_height = (f64::from(image_width) / image_aspect_ratio) as _;
+            samples_per_pixel = 100;
+            max_depth = 50;
+
+            // World.
+            world = random_scene();
+            background = Colour(0.7, 0.8, 1.0).into();
+
+            // Camera.
+            lookfrom = Vec3(13.0, 2.0, 3.0);
+            lookat = Vec3(0.0, 0.0, 0.0);
+            vup = Vec3(0.0, 1.0, 0.0);
+            vfov = 20.0;
+            aspect_ratio = f64::from(image_width) / f64::from(image_height);
+            aperture = 0.0;
+            focus = FocusTarget::Distance(10.0);
+            time0 = 0.0;
+            time1 = 1.0;
+            camera_type = CameraType::Stereo(Stereo::new(StereoFormat::SideBySide, 0.3, 10.0));
+        }
+
+        10 => {
+            // Image.
+            let image_aspect_ratio = 3.0 / 2.0;
+            image_width = 600;
+            image_height = (f64::from(image_width) / image_aspect_ratio) as _;
+            samples_per_pixel = 200;
+            max_depth = 50;
+
+            // World.
+            world = random_scene();
+            background = Colour(0.7, 0.8, 1.0).into();
+
+            // Camera: a 50 mm lens at f/2 on a full-frame sensor, with a scene unit of 10 cm,
+            // focused on the metal sphere in the centre of the image.  The hexagonal aperture
+            // gives hexagonal bokeh, squeezed into cat’s eyes towards the edges.
+            lookfrom = Vec3(13.0, 2.0, 3.0);
+            lookat = Vec3(4.0, 1.0, 0.0);
+            vup = Vec3(0.0, 1.0, 0.0);
+            vfov = 0.0; // Set by the lens.
+            aspect_ratio = f64::from(image_width) / f64::from(image_height);
+            aperture = 0.0; // Set by the lens.
+            focus = FocusTarget::Pixel(0.5, 0.5);
+            time0 = 0.0;
+            time1 = 1.0;
+            camera_type = CameraType::Physical {
+                sensor_width: 36.0,
+                sensor_height: 24.0,
+                focal_length: 50.0,
+                f_number: 2.0,
+                mm_per_unit: 100.0,
+                aperture: Aperture::new(6, 0.0, 0.5),
+            };
+        }
+
+        11 => {
+            // Image.
+            let image_aspect_ratio = 16.0 / 9.0;
+            image_width = 400;
+            image_height = (f64::from(image_width) / image_aspect_ratio) as _;
+            samples_per_pixel = 100;
+            max_depth = 50;
+
+            // World.
+            world = earth()?;
+            background = Colour(0.7, 0.8, 1.0).into();
+
+            // Camera, circling the globe once every eight seconds, rising and falling as it goes.
+            // A still image shows the first frame at 24 frames per second.
+            lookfrom = Vec3(13.0, 2.0, 3.0);
+            lookat = Vec3(0.0, 0.0, 0.0);
+            vup = Vec3(0.0, 1.0, 0.0);
+            vfov = 20.0;
+            aspect_ratio = f64::from(image_width) / f64::from(image_height);
+            aperture = 0.0;
+            focus = FocusTarget::Point(lookat);
+            time0 = 0.0;
+            time1 = 1.0 / 24.0;
+            let keys = (0..=8)
+                .map(|x| {
+                    let angle = f64::from(x) * 45.0_f64.to_radians();
+                    let height = if x % 2 == 0 { 2.0 } else { 5.0 };
+                    let position = Vec3(13.0 * angle.cos(), height, 13.0 * angle.sin());
+                    let distance = (position - lookat).length();
+                    CameraKey::new(f64::from(x), position, lookat, vup, vfov, distance)
+                })
+                .collect();
+            rig = Some(Arc::new(CameraRig::new(keys, Interpolation::CatmullRom)));
+        }
+
+        12 => {
+            // Image.
+            let image_aspect_ratio = 16.0 / 9.0;
+            image_width = 400;
+            image_height = (f64::from(image_width) / image_aspect_ratio) as _;
+            samples_per_pixel = 100;
+            max_depth = 50;
+
+            // World.
+            world = moving_blocks();
+            background = Colour(0.7, 0.8, 1.0).into();
+
+            // Camera.
+            lookfrom = Vec3(0.0, 2.0, 8.0);
+            lookat = Vec3(0.5, 0.8, 0.0);
+            vup = Vec3(0.0, 1.0, 0.0);
+            vfov = 35.0;
+            aspect_ratio = f64::from(image_width) / f64::from(image_height);
+            aperture = 0.0;
+            focus = FocusTarget::Point(lookat);
+            time0 = 0.0;
+            time1 = 1.0;
+        }
+
+        13 => {
+            // Image.
+            let image_aspect_ratio = 16.0 / 9.0;
+            image_width = 400;
+            image_height = (f64::from(image_width) / image_aspect_ratio) as _;
+            samples_per_pixel = 100;
+            max_depth = 50;
+
+            // World.
+            world = two_perlin_spheres();
+            background =
+                GradientBackground::new_background(Colour(1.0, 1.0, 1.0), Colour(0.5, 0.7, 1.0));
+
+            // Camera.
+            lookfrom = Vec3(13.0, 2.0, 3.0);
+            lookat = Vec3(0.0, 0.0, 0.0);
+            vup = Vec3(0.0, 1.0, 0.0);
+            vfov = 20.0;
+            aspect_ratio = f64::from(image_width) / f64::from(image_height);
+            aperture = 0.0;
+            focus = FocusTarget::Point(lookat);
+            time0 = 0.0;
+            time1 = 1.0;
+        }
+
+        14 => {
+            // Image.
+            let image_aspect_ratio = 16.0 / 9.0;
+            image_width = 400;
+            image_height = (f64::from(image_width) / image_aspect_ratio) as _;
+            samples_per_pixel = 100;
+            max_depth = 50;
+
+            // World.
+            world = lit_spheres();
+            background = Colour(0.0, 0.0, 0.0).into();
+            lights = vec![
+                PointLight::new_light(Vec3(-4.0, 4.0, 3.0), Colour(12.0, 10.0, 8.0)),
+                SpotLight::new_light(
+                    Vec3(3.0, 6.0, 2.0),
+                    Vec3(2.5, 0.0, 0.0),
+                    Colour(40.0, 40.0, 60.0),
+                    10.0,
+                    20.0,
+                    2.0,
+                ),
+                DirectionalLight::new_light(Vec3(1.0, -2.0, -1.0), Colour(0.1, 0.1, 0.12)),
+            ];
+
+            // Camera.
+            lookfrom = Vec3(0.0, 3.0, 10.0);
+            lookat = Vec3(0.0, 1.0, 0.0);
+            vup = Vec3(0.0, 1.0, 0.0);
+            vfov = 35.0;
+            aspect_ratio = f64::from(image_width) / f64::from(image_height);
+            aperture = 0.0;
+            focus = FocusTarget::Point(lookat);
+            time0 = 0.0;
+            time1 = 1.0;
+        }
+
+        15 => {
+            // Image.
+            let image_aspect_ratio = 16.0 / 9.0;
+            image_width = 400;
+            image_height = (f64::from(image_width) / image_aspect_ratio) as _;
+            samples_per_pixel = 100;
+            max_depth = 50;
+
+            // World, lit by a tilted downlight and a ceiling panel with the same profile.
+            let profile = Arc::new(IesProfile::parse(DOWNLIGHT_PROFILE)?);
+            world = photometric_spheres(&profile);
+            background = Colour(0.0, 0.0, 0.0).into();
+            let mut downlight = PointLight::new(Vec3(2.5, 4.0, 1.0), Colour(12.0, 12.0, 12.0));
+            downlight.set_profile(profile, Quaternion::new_rotation(Vec3(0.0, 0.0, 1.0), 20.0));
+            lights = vec![Arc::new(downlight)];
+
+            // Camera.
+            lookfrom = Vec3(0.0, 3.0, 10.0);
+            lookat = Vec3(0.0, 1.0, 0.0);
+            vup = Vec3(0.0, 1.0, 0.0);
+            vfov = 35.0;
+            aspect_ratio = f64::from(image_width) / f64::from(image_height);
+            aperture = 0.0;
+            focus = FocusTarget::Point(lookat);
+            time0 = 0.0;
+            time1 = 1.0;
+        }
+
+        16 => {
+            // Image.
+            let image_aspect_ratio = 16.0 / 9.0;
+            image_width = 400;
+            image_height = (f64::from(image_width) / image_aspect_ratio) as _;
+            samples_per_pixel = 100;
+            max_depth = 50;
+
+            // World.
+            let (eta, k) = ConductorPreset::Aluminium.ior();
+            world = sphere_row(vec![
+                Conductor::new_material_preset(ConductorPreset::Gold, 0.05),
+                Conductor::new_material_preset(ConductorPreset::Copper, 0.3),
+                Conductor::new_material_anisotropic(eta, k, 0.05, 0.5, 0.0),
+            ]);
+            background =
+                GradientBackground::new_background(Colour(1.0, 1.0, 1.0), Colour(0.5, 0.7, 1.0));
+
+            // Camera.
+            lookfrom = Vec3(0.0, 2.5, 9.0);
+            lookat = Vec3(0.0, 0.8, 0.0);
+            vup = Vec3(0.0, 1.0, 0.0);
+            vfov = 35.0;
+            aspect_ratio = f64::from(image_width) / f64::from(image_height);
+            aperture = 0.0;
+            focus = FocusTarget::Point(Vec3(0.0, 1.0, 0.0));
+            time0 = 0.0;
+            time1 = 1.0;
+        }
+
+        17 => {
+            // Image.
+            let image_aspect_ratio = 16.0 / 9.0;
+            image_width = 400;
+            image_height = (f64::from(image_width) / image_aspect_ratio) as _;
+            samples_per_pixel = 100;
+            max_depth = 50;
+
+            // World.
+            world = sphere_row(vec![
+                Dielectric::new_material_tinted(1.5, Colour(0.4, 0.8, 0.5), 1.0),
+                RoughDielectric::new_material(1.5, 0.1),
+                RoughDielectric::new_material(1.5, 0.4),
+            ]);
+            background =
+                GradientBackground::new_background(Colour(1.0, 1.0, 1.0), Colour(0.5, 0.7, 1.0));
+
+            // Camera.
+            lookfrom = Vec3(0.0, 2.5, 9.0);
+            lookat = Vec3(0.0, 0.8, 0.0);
+            vup = Vec3(0.0, 1.0, 0.0);
+            vfov = 35.0;
+            aspect_ratio = f64::from(image_width) / f64::from(image_height);
+            aperture = 0.0;
+            focus = FocusTarget::Point(Vec3(0.0, 1.0, 0.0));
+            time0 = 0.0;
+            time1 = 1.0;
+        }
+
+        18 => {
+            // Image.
+            let image_aspect_ratio = 16.0 / 9.0;
+            image_width = 400;
+            image_height = (f64::from(image_width) / image_aspect_ratio) as _;
+            samples_per_pixel = 200;
+            max_depth = 50;
+
+            // World.  The colours only separate when rendering spectrally.
+            world = dispersive_spheres();
+            background =
+                GradientBackground::new_background(Colour(1.0, 1.0, 1.0), Colour(0.5, 0.7, 1.0));
+            spectral = true;
+
+            // Camera.
+            lookfrom = Vec3(0.0, 2.5, 9.0);
+            lookat = Vec3(0.0, 0.8, 0.0);
+            vup = Vec3(0.0, 1.0, 0.0);
+            vfov = 35.0;
+            aspect_ratio = f64::from(image_width) / f64::from(image_height);
+            aperture = 0.0;
+            focus = FocusTarget::Point(Vec3(0.0, 1.0, 0.0));
+            time0 = 0.0;
+            time1 = 1.0;
+        }
+
+        19 => {
+            // Image.
+            let image_aspect_ratio = 16.0 / 9.0;
+            image_width = 400;
+            image_height = (f64::from(image_width) / image_aspect_ratio) as _;
+            samples_per_pixel = 100;
+            max_depth = 50;
+
+            // World.
+            let plastic = {
+                let mut plastic = Principled::new(Colour(0.7, 0.1, 0.1), 0.0, 0.4);
+                plastic.set_clearcoat(1.0);
+                Arc::new(plastic)
+            };
+            let glass = {
+                let mut glass = Principled::new(Colour(0.8, 0.9, 1.0), 0.0, 0.05);
+                glass.set_transmission(1.0);
+                Arc::new(glass)
+            };
+            world = sphere_row(vec![
+                plastic,
+                Principled::new_material(Colour(0.9, 0.6, 0.2), 1.0, 0.25),
+                glass,
+            ]);
+            background =
+                GradientBackground::new_background(Colour(1.0, 1.0, 1.0), Colour(0.5, 0.7, 1.0));
+
+            // Camera.
+            lookfrom = Vec3(0.0, 2.5, 9.0);
+            lookat = Vec3(0.0, 0.8, 0.0);
+            vup = Vec3(0.0, 1.0, 0.0);
+            vfov = 35.0;
+            aspect_ratio = f64::from(image_width) / f64::from(image_height);
+            aperture = 0.0;
+            focus = FocusTarget::Point(Vec3(0.0, 1.0, 0.0));
+            time0 = 0.0;
+            time1 = 1.0;
+        }
+
+        20 => {
+            // Image.
+            let image_aspect_ratio = 16.0 / 9.0;
+            image_width = 400;
+            image_height = (f64::from(image_width) / image_aspect_ratio) as _;
+            samples_per_pixel = 100;
+            max_depth = 50;
+
+            // World.
+            world = sphere_row(vec![
+                Coated::new_material(Lambertian2::new_material(Colour(0.1, 0.2, 0.6)), 1.5, 0.0),
+                Coated::new_material_absorbing(
+                    Conductor::new_material_preset(ConductorPreset::Silver, 0.2),
+                    1.5,
+                    0.05,
+                    Colour(0.2, 1.0, 2.0),
+                    1.0,
+                ),
+                Coated::new_material(Lambertian2::new_material(Colour(0.7, 0.7, 0.7)), 1.5, 0.3),
+            ]);
+            background =
+                GradientBackground::new_background(Colour(1.0, 1.0, 1.0), Colour(0.5, 0.7, 1.0));
+
+            // Camera.
+            lookfrom = Vec3(0.0, 2.5, 9.0);
+            lookat = Vec3(0.0, 0.8, 0.0);
+            vup = Vec3(0.0, 1.0, 0.0);
+            vfov = 35.0;
+            aspect_ratio = f64::from(image_width) / f64::from(image_height);
+            aperture = 0.0;
+            focus = FocusTarget::Point(Vec3(0.0, 1.0, 0.0));
+            time0 = 0.0;
+            time1 = 1.0;
+        }
+
+        21 => {
+            // Image.
+            let image_aspect_ratio = 16.0 / 9.0;
+            image_width = 400;
+            image_height = (f64::from(image_width) / image_aspect_ratio) as _;
+            samples_per_pixel = 100;
+            max_depth = 50;
+
+            // World.
+            let metal = Conductor::new_material_preset(ConductorPreset::Iron, 0.2);
+            let rust = Lambertian2::new_material(Colour(0.4, 0.15, 0.05));
+            let glow = DiffuseLight::new_material_with_intensity(
+                Chequer::new_texture(
+                    Vec3(8.0, 8.0, 8.0),
+                    Colour(0.0, 0.0, 0.0),
+                    Colour(1.0, 0.5, 0.1),
+                ),
+                2.0,
+                false,
+            );
+            world = sphere_row(vec![
+                Mix::new_material_textured(metal.clone(), rust, Noise::new_texture(2.0)),
+                Mix::new_material(
+                    metal.clone(),
+                    Lambertian2::new_material(Colour(0.7, 0.7, 0.7)),
+                    0.5,
+                ),
+                AddMaterial::new_material(metal, glow),
+            ]);
+            background =
+                GradientBackground::new_background(Colour(1.0, 1.0, 1.0), Colour(0.5, 0.7, 1.0));
+
+            // Camera.
+            lookfrom = Vec3(0.0, 2.5, 9.0);
+            lookat = Vec3(0.0, 0.8, 0.0);
+            vup = Vec3(0.0, 1.0, 0.0);
+            vfov = 35.0;
+            aspect_ratio = f64::from(image_width) / f64::from(image_height);
+            aperture = 0.0;
+            focus = FocusTarget::Point(Vec3(0.0, 1.0, 0.0));
+            time0 = 0.0;
+            time1 = 1.0;
+        }
+
+        22 => {
+            // Image.
+            let image_aspect_ratio = 16.0 / 9.0;
+            image_width = 400;
+            image_height = (f64::from(image_width) / image_aspect_ratio) as _;
+            samples_per_pixel = 100;
+            max_depth = 50;
+
+            // World.
+            let clay = Colour(0.7, 0.45, 0.3);
+            world = sphere_row(vec![
+                Lambertian2::new_material(clay),
+                OrenNayar::new_material(clay, 20.0),
+                OrenNayar::new_material(clay, 60.0),
+            ]);
+            background = Colour(0.05, 0.05, 0.06).into();
+            lights = vec![DirectionalLight::new_light(
+                Vec3(-1.0, -1.0, -2.0),
+                Colour(3.0, 3.0, 3.0),
+            )];
+
+            // Camera.
+            lookfrom = Vec3(0.0, 2.5, 9.0);
+            lookat = Vec3(0.0, 0.8, 0.0);
+            vup = Vec3(0.0, 1.0, 0.0);
+            vfov = 35.0;
+            aspect_ratio = f64::from(image_width) / f64::from(image_height);
+            aperture = 0.0;
+            focus = FocusTarget::Point(Vec3(0.0, 1.0, 0.0));
             time0 = 0.0;
             time1 = 1.0;
         }
@@ -550,31 +1237,101 @@ fn render(scene: u32, output: &mut dyn Write) -> Result<(), Box<dyn Error + Send
         x => return Err(format!("invalid scene number: {}", x).into()),
     }
 
-    let cam = Camera::new(
-        lookfrom,
-        lookat,
-        vup,
-        vfov,
-        aspect_ratio,
-        aperture,
-        dist_to_focus,
-        time0..time1,
-    );
-    let cam = Arc::new(cam);
+    let mut cam = match camera_type {
+        CameraType::ThinLens => Camera::new(
+            lookfrom,
+            lookat,
+            vup,
+            vfov,
+            aspect_ratio,
+            aperture,
+            1.0, // Replaced by the focus target below.
+            time0..time1,
+        ),
+
+        CameraType::Stereo(stereo) => Camera::new_stereo(
+            lookfrom,
+            lookat,
+            vup,
+            vfov,
+            aspect_ratio,
+            aperture,
+            1.0, // Replaced by the focus target below.
+            time0..time1,
+            stereo,
+        ),
+
+        CameraType::Physical {
+            sensor_width,
+            sensor_height,
+            focal_length,
+            f_number,
+            mm_per_unit,
+            aperture,
+        } => Camera::new_physical(
+            lookfrom,
+            lookat,
+            vup,
+            sensor_width,
+            sensor_height,
+            focal_length,
+            f_number,
+            (lookat - lookfrom).length(), // Refined by the focus target below.
+            mm_per_unit,
+            aperture,
+            time0..time1,
+        ),
+    };
+    cam.focus_on(focus, &world)?;
+    if let Some(rig) = rig {
+        cam.set_rig(rig);
+    }
+
+    let background = environment.unwrap_or(background);
 
     // Render.
 
-    rays::run(
-        world,
-        background,
-        image_width,
-        image_height,
-        samples_per_pixel,
-        max_depth,
-        cam,
-        output,
-        true,
-    )
+    match output {
+        Output::Stream(output) => rays::run(
+            Arc::new(world),
+            background,
+            &lights,
+            image_width,
+            image_height,
+            samples_per_pixel,
+            max_depth,
+            Arc::new(cam),
+            filter,
+            sampler,
+            spectral,
+            white_balance,
+            output,
+            true,
+        ),
+
+        Output::Sequence {
+            frames,
+            fps,
+            filename_pattern,
+        } => rays::run_sequence(
+            &world,
+            background,
+            &lights,
+            image_width,
+            image_height,
+            samples_per_pixel,
+            max_depth,
+            &cam,
+            filter,
+            sampler,
+            spectral,
+            white_balance,
+            frames,
+            fps,
+            filename_pattern,
+            true,
+        ),
+    }
 }
 
 fn scene_number(arg: &OsStr) -> Result<u32, Box<dyn Error + Send + Sync>> {
@@ -586,25 +1343,282 @@ fn scene_number(arg: &OsStr) -> Result<u32, Box<dyn Error + Send + Sync>> {
     }
 }
 
+/**
+ * Parses a frame range of the form `FIRST-LAST`, or a single frame number.
+ */
+fn frame_range(arg: &OsStr) -> Result<RangeInclusive<u32>, Box<dyn Error + Send + Sync>> {
+    let invalid = || format!("invalid frame range “{}”", arg.to_string_lossy());
+
+    let arg = arg.to_str().ok_or_else(invalid)?;
+    let (first, last) = arg.split_once('-').unwrap_or((arg, arg));
+    let first: u32 = first.parse().map_err(|x| format!("{}: {}", invalid(), x))?;
+    let last: u32 = last.parse().map_err(|x| format!("{}: {}", invalid(), x))?;
+
+    if first > last {
+        return Err(invalid().into());
+    }
+
+    Ok(first..=last)
+}
+
+fn frame_rate(arg: &OsStr) -> Result<f64, Box<dyn Error + Send + Sync>> {
+    match arg.to_str().map(str::parse::<f64>) {
+        Some(Ok(fps)) if fps > 0.0 => Ok(fps),
+        _ => Err(format!("invalid frame rate “{}”", arg.to_string_lossy()).into()),
+    }
+}
+
+/**
+ * Parses sky parameters of the form `ELEVATION:AZIMUTH` or `ELEVATION:AZIMUTH:TURBIDITY`.
+ */
+fn sky(arg: &OsStr) -> Result<(f64, f64, f64), Box<dyn Error + Send + Sync>> {
+    let invalid = || format!("invalid sky “{}”", arg.to_string_lossy());
+
+    let arg = arg.to_str().ok_or_else(invalid)?;
+    let values = arg
+        .split(':')
+        .map(str::parse::<f64>)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|x| format!("{}: {}", invalid(), x))?;
+
+    let (sun_elevation, sun_azimuth, turbidity) = match values[..] {
+        [sun_elevation, sun_azimuth] => (sun_elevation, sun_azimuth, 3.0),
+        [sun_elevation, sun_azimuth, turbidity] => (sun_elevation, sun_azimuth, turbidity),
+        _ => return Err(invalid().into()),
+    };
+
+    if !(0.0..=90.0).contains(&sun_elevation)
+        || !sun_azimuth.is_finite()
+        || !(1.0..=20.0).contains(&turbidity)
+    {
+        return Err(invalid().into());
+    }
+
+    Ok((sun_elevation, sun_azimuth, turbidity))
+}
+
+fn white_balance(arg: &OsStr) -> Result<f64, Box<dyn Error + Send + Sync>> {
+    match arg.to_str().map(str::parse::<f64>) {
+        Some(Ok(temperature)) if temperature >= 500.0 && temperature.is_finite() => Ok(temperature),
+        _ => Err(format!("invalid colour temperature “{}”", arg.to_string_lossy()).into()),
+    }
+}
+
+fn environment_rotation(arg: &OsStr) -> Result<f64, Box<dyn Error + Send + Sync>> {
+    match arg.to_str().map(str::parse::<f64>) {
+        Some(Ok(rotation)) if rotation.is_finite() => Ok(rotation),
+        _ => Err(format!("invalid environment rotation “{}”", arg.to_string_lossy()).into()),
+    }
+}
+
+fn environment_intensity(arg: &OsStr) -> Result<f64, Box<dyn Error + Send + Sync>> {
+    match arg.to_str().map(str::parse::<f64>) {
+        Some(Ok(intensity)) if intensity >= 0.0 && intensity.is_finite() => Ok(intensity),
+        _ => Err(format!("invalid environment intensity “{}”", arg.to_string_lossy()).into()),
+    }
+}
+
+/**
+ * Parses a reconstruction filter of the form `NAME` or `NAME:RADIUS`.
+ */
+fn filter(arg: &OsStr) -> Result<Arc<dyn Filter>, Box<dyn Error + Send + Sync>> {
+    let invalid = || format!("invalid filter “{}”", arg.to_string_lossy());
+
+    let arg = arg.to_str().ok_or_else(invalid)?;
+    let (name, radius) = match arg.split_once(':') {
+        Some((name, radius)) => match radius.parse::<f64>() {
+            Ok(radius) if radius > 0.0 => (name, Some(radius)),
+            _ => return Err(invalid().into()),
+        },
+        None => (arg, None),
+    };
+
+    let filter = match name {
+        "box" => BoxFilter::new_filter(radius.unwrap_or(0.5)),
+        "tent" => TentFilter::new_filter(radius.unwrap_or(1.0)),
+        "gaussian" => GaussianFilter::new_filter(radius.unwrap_or(1.5), 0.5),
+        "mitchell" => MitchellFilter::new_filter(radius.unwrap_or(2.0), 1.0 / 3.0, 1.0 / 3.0),
+        "lanczos" => LanczosFilter::new_filter(radius.unwrap_or(2.0)),
+        _ => return Err(invalid().into()),
+    };
+
+    Ok(filter)
+}
+
+/**
+ * Parses a sampler name.
+ */
+fn sampler(arg: &OsStr) -> Result<Box<dyn Sampler>, Box<dyn Error + Send + Sync>> {
+    let sampler = match arg.to_str() {
+        Some("independent") => IndependentSampler::new_sampler(),
+        Some("stratified") => StratifiedSampler::new_sampler(0),
+        Some("halton") => HaltonSampler::new_sampler(0),
+        Some("sobol") => SobolSampler::new_sampler(0),
+        Some("bluenoise") => BlueNoiseSampler::new_sampler(0),
+        _ => return Err(format!("invalid sampler “{}”", arg.to_string_lossy()).into()),
+    };
+
+    Ok(sampler)
+}
+
 /**
  * Runs the program.
  */
 fn run(args: &[OsString]) -> Result<(), Box<dyn Error + Send + Sync>> {
-    match args.len() {
-        0 | 1 => return Err("no scene number specified".into()),
+    // Separate options from positional arguments.
+    let mut positional = Vec::new();
+    let mut frames = None;
+    let mut fps = None;
+    let mut filter = BoxFilter::new_filter(0.5);
+    let mut sampler = IndependentSampler::new_sampler();
+    let mut environment = None;
+    let mut environment_rotation = None;
+    let mut environment_intensity = None;
+    let mut sky = None;
+    let mut spectral = false;
+    let mut white_balance = None;
+
+    let mut args = args.iter().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.to_str() {
+            Some("--frames") => match args.next() {
+                Some(arg) => frames = Some(frame_range(arg)?),
+                None => return Err("option “--frames” requires an argument".into()),
+            },
+
+            Some("--fps") => match args.next() {
+                Some(arg) => fps = Some(frame_rate(arg)?),
+                None => return Err("option “--fps” requires an argument".into()),
+            },
+
+            Some("--filter") => match args.next() {
+                Some(arg) => filter = self::filter(arg)?,
+                None => return Err("option “--filter” requires an argument".into()),
+            },
+
+            Some("--sampler") => match args.next() {
+                Some(arg) => sampler = self::sampler(arg)?,
+                None => return Err("option “--sampler” requires an argument".into()),
+            },
+
+            Some("--environment") => match args.next() {
+                Some(arg) => environment = Some(Path::new(arg)),
+                None => return Err("option “--environment” requires an argument".into()),
+            },
+
+            Some("--spectral") => spectral = true,
+
+            Some("--white-balance") => match args.next() {
+                Some(arg) => white_balance = Some(self::white_balance(arg)?),
+                None => return Err("option “--white-balance” requires an argument".into()),
+            },
+
+            Some("--sky") => match args.next() {
+                Some(arg) => sky = Some(self::sky(arg)?),
+                None => return Err("option “--sky” requires an argument".into()),
+            },
+
+            Some("--environment-rotation") => match args.next() {
+                Some(arg) => environment_rotation = Some(self::environment_rotation(arg)?),
+                None => return Err("option “--environment-rotation” requires an argument".into()),
+            },
+
+            Some("--environment-intensity") => match args.next() {
+                Some(arg) => environment_intensity = Some(self::environment_intensity(arg)?),
+                None => return Err("option “--environment-intensity” requires an argument".into()),
+            },
+
+            _ => positional.push(arg),
+        }
+    }
 
-        2 => {
-            let scene = scene_number(&args[1])?;
+    let environment = match (environment, sky) {
+        (Some(_), Some(_)) => {
+            return Err("options “--environment” and “--sky” cannot be used together".into())
+        }
+        (Some(filename), None) => Some(EnvironmentMap::new_background(
+            filename,
+            environment_rotation.unwrap_or(0.0),
+            environment_intensity.unwrap_or(1.0),
+        )?),
+        (None, _) if environment_rotation.is_some() => {
+            return Err("option “--environment-rotation” requires “--environment”".into())
+        }
+        (None, Some((sun_elevation, sun_azimuth, turbidity))) => Some(Sky::new_background(
+            sun_elevation,
+            sun_azimuth,
+            turbidity,
+            SUN_ANGULAR_DIAMETER,
+            SKY_INTENSITY * environment_intensity.unwrap_or(1.0),
+        )),
+        (None, None) if environment_intensity.is_some() => {
+            return Err(
+                "option “--environment-intensity” requires “--environment” or “--sky”".into(),
+            )
+        }
+        (None, None) => None,
+    };
+
+    if let Some(frames) = frames {
+        let (scene, filename_pattern) = match positional.len() {
+            0 => return Err("no scene number specified".into()),
+            1 => (scene_number(positional[0])?, "out_%04d.png"),
+            2 => match positional[1].to_str() {
+                Some(filename_pattern) => (scene_number(positional[0])?, filename_pattern),
+                None => {
+                    return Err(format!(
+                        "invalid output file name pattern “{}”",
+                        positional[1].to_string_lossy()
+                    )
+                    .into())
+                }
+            },
+            _ => return Err("too many command-line arguments".into()),
+        };
+
+        let output = Output::Sequence {
+            frames,
+            fps: fps.unwrap_or(24.0),
+            filename_pattern,
+        };
+        return render(
+            scene,
+            filter,
+            sampler.as_ref(),
+            environment,
+            spectral,
+            white_balance,
+            output,
+        );
+    }
+
+    if fps.is_some() {
+        return Err("option “--fps” requires “--frames”".into());
+    }
+
+    match positional.len() {
+        0 => return Err("no scene number specified".into()),
+
+        1 => {
+            let scene = scene_number(positional[0])?;
 
             // No output file name specified on command-line.  Use stdout.
-            render(scene, &mut io::stdout().lock())?;
+            render(
+                scene,
+                filter,
+                sampler.as_ref(),
+                environment,
+                spectral,
+                white_balance,
+                Output::Stream(&mut io::stdout().lock()),
+            )?;
         }
 
-        3 => {
-            let scene = scene_number(&args[1])?;
+        2 => {
+            let scene = scene_number(positional[0])?;
 
             // Get the output file name from the command-line.
-            let filename = Path::new(&args[2]);
+            let filename = Path::new(positional[1]);
 
             let mut output = match File::create(filename) {
                 Ok(output) => BufWriter::new(output),
@@ -615,7 +1629,15 @@ fn run(args: &[OsString]) -> Result<(), Box<dyn Error + Send + Sync>> {
                 }
             };
 
-            render(scene, &mut output)?;
+            render(
+                scene,
+                filter,
+                sampler.as_ref(),
+                environment,
+                spectral,
+                white_balance,
+                Output::Stream(&mut output),
+            )?;
 
             if let Err(x) = output.flush() {
                 return Err(format!("error writing to “{}”: {}", filename.display(), x).into());
@@ -635,7 +1657,7 @@ fn run(args: &[OsString]) -> Result<(), Box<dyn Error + Send + Sync>> {
  */
 #[must_use]
 fn get_progname(args: &[OsString]) -> &str {
-    if let Some(progname) = args.get(0) {
+    if let Some(progname) = args.first() {
         if let Some(progname) = Path::new(progname).file_name() {
             if let Some(progname) = progname.to_str() {
                 if !progname.is_empty() {
@@ -650,7 +1672,31 @@ fn get_progname(args: &[OsString]) -> &str {
 /**
  * Entry point.
  *
- * Usage: `rays SCENE_NUMBER [OUTPUT_FILE]`
+ * Usage: `rays SCENE_NUMBER [OUTPUT_FILE] [--filter NAME[:RADIUS]] [--sampler NAME]`
+ *
+ * or, for an animation: `rays SCENE_NUMBER [OUTPUT_PATTERN] --frames FIRST-LAST [--fps FPS]`,
+ * where `OUTPUT_PATTERN` defaults to `out_%04d.png`.
+ *
+ * The reconstruction filter `NAME` is one of `box` (the default), `tent`, `gaussian`, `mitchell`
+ * or `lanczos`.
+ *
+ * The sampler `NAME` is one of `independent` (the default), `stratified`, `halton`, `sobol` or
+ * `bluenoise`.
+ *
+ * `--environment FILE` replaces the scene’s background with an equirectangular environment map,
+ * such as a Radiance `.hdr` or OpenEXR `.exr` image, optionally rotated about the vertical axis by
+ * `--environment-rotation DEGREES` and scaled in brightness by `--environment-intensity SCALE`.
+ *
+ * Alternatively, `--sky ELEVATION:AZIMUTH[:TURBIDITY]` replaces the background with a daylight sky
+ * and sun, with the sun’s elevation and azimuth in degrees (an azimuth of 0 being along −z) and a
+ * turbidity of 3 by default.  Its brightness can also be scaled by `--environment-intensity`.
+ *
+ * `--spectral` traces several wavelengths of light for each sample, with spectra upsampled from
+ * the colours in the scene, so that dispersive glass splits white light into colours.  Scenes
+ * that need it are always rendered spectrally.
+ *
+ * `--white-balance KELVIN` adjusts the output colours so that light of the given colour
+ * temperature appears white.
  */
 fn main() {
     let args: Vec<_> = env::args_os().collect();
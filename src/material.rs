@@ -1,25 +1,91 @@
+mod coated;
+mod conductor;
 mod dielectric;
 mod diffuselight;
 mod isotropic;
 mod lambertian;
 mod metal;
-use crate::{Colour, HitRecord, Ray, Vec3};
-pub use dielectric::Dielectric;
+mod microfacet;
+mod mix;
+mod orennayar;
+mod principled;
+mod roughdielectric;
+use crate::{Colour, HitRecord, Ray, Sampler, Vec3};
+pub use coated::Coated;
+pub use conductor::{Conductor, ConductorPreset};
+pub use dielectric::{Dielectric, RefractiveIndex};
 pub use diffuselight::DiffuseLight;
 pub use isotropic::Isotropic;
 pub use lambertian::{Lambertian0, Lambertian1, Lambertian2};
 pub use metal::Metal;
+pub use mix::{AddMaterial, Mix};
+pub use orennayar::OrenNayar;
+pub use principled::Principled;
+pub use roughdielectric::RoughDielectric;
 use std::fmt::Debug;
 
 /**
  * Trait for materials.
  */
 pub trait Material: Debug + Send + Sync {
+    /**
+     * Scatters an incoming ray, using `sampler` for any random choices.  Returns the attenuation
+     * and the scattered ray, or `None` if the ray is absorbed.
+     */
     #[must_use]
-    fn scatter(&self, r_in: &Ray, rec: &HitRecord) -> Option<(Colour, Ray)>;
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        sampler: &mut dyn Sampler,
+    ) -> Option<(Colour, Ray)>;
 
+    /**
+     * Evaluates scattering towards `direction`, for lighting sampled directly.  Returns the
+     * fraction of light arriving from `direction` that leaves back along the incoming ray (the
+     * BSDF times the cosine of the angle to the normal), and the probability density, per unit
+     * solid angle, of `scatter` choosing `direction`.  Returns `None` if light from `direction`
+     * is not scattered, or for materials that only scatter in discrete directions.
+     */
     #[must_use]
-    fn emitted(&self, _u: f64, _v: f64, _p: Vec3) -> Colour {
+    fn eval(&self, _r_in: &Ray, _rec: &HitRecord, _direction: Vec3) -> Option<(Colour, f64)> {
+        None
+    }
+
+    /**
+     * Returns `false` for materials that never scatter light, only emit it, so that materials
+     * combining them with others need not sample them.
+     */
+    #[must_use]
+    fn scatters(&self) -> bool {
+        true
+    }
+
+    /**
+     * Returns `true` if the direction in which the material scatters light depends on its
+     * wavelength, so that a ray carrying several wavelengths can only follow it with the hero
+     * wavelength.
+     */
+    #[must_use]
+    fn is_dispersive(&self) -> bool {
+        false
+    }
+
+    /**
+     * Returns the fraction of light transmitted along the incoming ray `r_in` from the hit `rec`
+     * back to the ray’s origin, through the interior of the material.  A ray hitting the back of
+     * a surface has travelled inside the material since leaving its origin.
+     */
+    #[must_use]
+    fn transmittance(&self, _r_in: &Ray, _rec: &HitRecord) -> Colour {
+        Colour(1.0, 1.0, 1.0)
+    }
+
+    /**
+     * Returns the light emitted at `rec` back along the incoming ray `r_in`.
+     */
+    #[must_use]
+    fn emitted(&self, _r_in: &Ray, _rec: &HitRecord) -> Colour {
         Colour(0.0, 0.0, 0.0)
     }
 }
@@ -0,0 +1,388 @@
+use super::{
+    microfacet::{fresnel_dielectric, Frame, Ggx, MIN_ROUGHNESS},
+    Material,
+};
+use crate::{Colour, HitRecord, Ray, Sampler, SolidColour, Texture, Vec3};
+use std::{f64::consts, sync::Arc};
+
+/**
+ * Type for representing a material under a clear dielectric coat, such as car paint, varnished
+ * wood or lacquered plastic.
+ *
+ * Light is reflected by the coat, whose surface may be rough, in proportion to the exact Fresnel
+ * equations, and the rest is refracted through the coat to the base and back out (after Weidlich
+ * and Wilkie).  Light reflected back down at the underside of the coat is accounted for
+ * approximately, as bouncing between the coat and the diffuse part of the base.  The coat may
+ * absorb light, with `absorption` its absorption coefficient per unit length and `thickness` its
+ * thickness, so that its colour deepens at grazing angles.
+ *
+ * The roughness is in [0, 1], and when it comes from a texture, the luminance of the texture is
+ * used.  The base must be opaque, and is left uncoated where it is seen from behind.
+ */
+#[derive(Debug)]
+pub struct Coated {
+    base: Arc<dyn Material>,
+    ir: f64, // Index of refraction of the coat.
+    roughness: Arc<dyn Texture>,
+    optical_depth: Colour, // Absorption through the thickness of the coat.
+}
+
+impl Coated {
+    /**
+     * Creates a clear coat with index of refraction `ir` and a uniform `roughness` over `base`.
+     */
+    #[must_use]
+    pub fn new(base: Arc<dyn Material>, ir: f64, roughness: f64) -> Self {
+        Self::new_absorbing(base, ir, roughness, Colour(0.0, 0.0, 0.0), 0.0)
+    }
+
+    #[must_use]
+    pub fn new_material(base: Arc<dyn Material>, ir: f64, roughness: f64) -> Arc<dyn Material> {
+        Arc::new(Self::new(base, ir, roughness))
+    }
+
+    /**
+     * Creates a clear coat with index of refraction `ir` and a roughness varying over the
+     * surface, over `base`.
+     */
+    #[must_use]
+    pub fn new_textured(
+        base: Arc<dyn Material>,
+        ir: f64,
+        roughness: impl Into<Arc<dyn Texture>>,
+    ) -> Self {
+        assert!(ir > 1.0);
+        Self {
+            base,
+            ir,
+            roughness: roughness.into(),
+            optical_depth: Colour(0.0, 0.0, 0.0),
+        }
+    }
+
+    #[must_use]
+    pub fn new_material_textured(
+        base: Arc<dyn Material>,
+        ir: f64,
+        roughness: impl Into<Arc<dyn Texture>>,
+    ) -> Arc<dyn Material> {
+        Arc::new(Self::new_textured(base, ir, roughness))
+    }
+
+    /**
+     * Creates a coat with index of refraction `ir`, a uniform `roughness`, absorption coefficient
+     * `absorption` per unit length and `thickness`, over `base`.
+     */
+    #[must_use]
+    pub fn new_absorbing(
+        base: Arc<dyn Material>,
+        ir: f64,
+        roughness: f64,
+        absorption: Colour,
+        thickness: f64,
+    ) -> Self {
+        assert!(absorption.0 >= 0.0 && absorption.1 >= 0.0 && absorption.2 >= 0.0);
+        assert!(thickness >= 0.0);
+        Self {
+            optical_depth: thickness * absorption,
+            ..Self::new_textured(
+                base,
+                ir,
+                SolidColour::new_texture(Colour(roughness, roughness, roughness)),
+            )
+        }
+    }
+
+    #[must_use]
+    pub fn new_material_absorbing(
+        base: Arc<dyn Material>,
+        ir: f64,
+        roughness: f64,
+        absorption: Colour,
+        thickness: f64,
+    ) -> Arc<dyn Material> {
+        Arc::new(Self::new_absorbing(
+            base, ir, roughness, absorption, thickness,
+        ))
+    }
+
+    /**
+     * Returns the microfacet distribution of the coat and the local frame at `rec`.
+     */
+    #[must_use]
+    fn distribution(&self, rec: &HitRecord) -> (Ggx, Frame) {
+        let roughness = self
+            .roughness
+            .value(rec.u(), rec.v(), rec.p())
+            .luminance()
+            .max(MIN_ROUGHNESS);
+        (
+            Ggx::new(roughness, roughness),
+            Frame::new(rec.normal(), rec.tangent()),
+        )
+    }
+
+    /**
+     * Returns the ray inside the coat arriving at the base at `rec` and leaving in the direction
+     * `wo`, in the local frame `frame`, and the hit on the base.
+     */
+    #[must_use]
+    fn inner_hit(&self, r_in: &Ray, rec: &HitRecord, frame: Frame, wo: Vec3) -> (Ray, HitRecord) {
+        let direction = -frame.to_world(wo);
+        let r = Ray::new(rec.p() - direction, direction, r_in.time())
+            .with_wavelengths(r_in.wavelengths());
+        let rec = HitRecord::new(
+            &r,
+            rec.p(),
+            rec.normal(),
+            rec.tangent(),
+            rec.t(),
+            rec.u(),
+            rec.v(),
+            Arc::clone(&self.base),
+        );
+        (r, rec)
+    }
+
+    /**
+     * Returns the fraction of light not absorbed over a path through the coat `path` times its
+     * thickness.
+     */
+    #[must_use]
+    fn attenuation(&self, path: f64) -> Colour {
+        let Colour(r, g, b) = self.optical_depth;
+        Colour((-r * path).exp(), (-g * path).exp(), (-b * path).exp())
+    }
+
+    /**
+     * Returns the probability of sampling the coat rather than the base, for `wo`.
+     */
+    #[must_use]
+    fn coat_probability(&self, wo: Vec3) -> f64 {
+        fresnel_dielectric(wo.z(), self.ir).max(0.05)
+    }
+
+    /**
+     * Returns how diffuse the base is, from 0 for a glossy base to 1 for a Lambertian one, judged
+     * from its sampling density in the mirror direction of the inner direction `wo`.  Returns
+     * `None` if the base only scatters in discrete directions.
+     */
+    #[must_use]
+    fn diffuseness(
+        &self,
+        r_inner: &Ray,
+        rec_inner: &HitRecord,
+        frame: Frame,
+        wo: Vec3,
+    ) -> Option<f64> {
+        let mirror = Vec3(-wo.x(), -wo.y(), wo.z());
+        let (_, pdf) = self.base.eval(r_inner, rec_inner, frame.to_world(mirror))?;
+        Some((mirror.z() * consts::FRAC_1_PI / pdf).min(1.0))
+    }
+
+    /**
+     * Returns the light arriving from `wi` reflected towards `wo` by the coat and by the base,
+     * and the probability density of sampling `wi`, given the base’s value and density for the
+     * inner directions and its `diffuseness`.
+     */
+    #[must_use]
+    fn combine(
+        &self,
+        ggx: Ggx,
+        wo: Vec3,
+        wi: Vec3,
+        base: Option<(Colour, f64)>,
+        diffuseness: f64,
+    ) -> Option<(Colour, f64)> {
+        if wi.z() <= 0.0 {
+            return None;
+        }
+        let coat_probability = self.coat_probability(wo);
+        let mut value = Colour(0.0, 0.0, 0.0);
+        let mut pdf = 0.0;
+
+        let h = (wo + wi).unit();
+        if wo.dot(h) > 0.0 {
+            let v =
+                ggx.d(h) * ggx.g(wo, wi) * fresnel_dielectric(wo.dot(h), self.ir) / (4.0 * wo.z());
+            value += Colour(v, v, v);
+            pdf += coat_probability * ggx.visible_normal_pdf(wo, h) / (4.0 * wo.dot(h));
+        }
+
+        match base {
+            Some((base_value, base_pdf)) if base_pdf > 0.0 => {
+                let (wo_inner, wi_inner) = (refract_in(wo, self.ir), refract_in(wi, self.ir));
+                let transmittance = self.attenuation(wo_inner.z().recip() + wi_inner.z().recip());
+
+                // The base scatters into the narrower cone of directions inside the coat.
+                let jacobian = wi.z() / (self.ir * self.ir * wi_inner.z());
+                let fresnel = (1.0 - fresnel_dielectric(wo.z(), self.ir))
+                    * (1.0 - fresnel_dielectric(wi.z(), self.ir));
+
+                // Light reflected back down by the underside of the coat bounces between it and
+                // the base.  Only diffuse bases lose much light to total internal reflection, so
+                // this is weighted by how diffuse the base is.
+                let albedo = (base_pdf.recip() * base_value) * transmittance;
+                let internal = internal_diffuse_reflectance(self.ir);
+                let bounces = |albedo: f64| {
+                    let reflected = internal * albedo.clamp(0.0, 1.0);
+                    1.0 + diffuseness * reflected / (1.0 - reflected)
+                };
+                let bounces = Colour(bounces(albedo.0), bounces(albedo.1), bounces(albedo.2));
+
+                value += (fresnel * jacobian) * transmittance * bounces * base_value;
+                pdf += (1.0 - coat_probability) * base_pdf * jacobian;
+            }
+            _ => {}
+        }
+
+        if pdf > 0.0 {
+            Some((value, pdf))
+        } else {
+            None
+        }
+    }
+}
+
+/**
+ * Refracts the direction `w` above the surface into a coat with index of refraction `ir`, giving
+ * the direction inside the coat leaving the base towards `w`.
+ */
+fn refract_in(w: Vec3, ir: f64) -> Vec3 {
+    let (x, y) = (w.x() / ir, w.y() / ir);
+    Vec3(x, y, (1.0 - x * x - y * y).max(0.0).sqrt())
+}
+
+/**
+ * Refracts the direction `w` inside a coat with index of refraction `ir` out through its surface,
+ * or returns `None` if it is totally internally reflected.
+ */
+fn refract_out(w: Vec3, ir: f64) -> Option<Vec3> {
+    let (x, y) = (w.x() * ir, w.y() * ir);
+    let z2 = 1.0 - x * x - y * y;
+    if z2 > 0.0 {
+        Some(Vec3(x, y, z2.sqrt()))
+    } else {
+        None
+    }
+}
+
+/**
+ * Returns the fraction of diffuse light inside a coat with index of refraction `ir` reflected
+ * back down at its surface, from the fit of Egan and Hilgeman.
+ */
+fn internal_diffuse_reflectance(ir: f64) -> f64 {
+    -1.440 / (ir * ir) + 0.710 / ir + 0.668 + 0.0636 * ir
+}
+
+impl Material for Coated {
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        sampler: &mut dyn Sampler,
+    ) -> Option<(Colour, Ray)> {
+        if !rec.front_face() {
+            return self.base.scatter(r_in, rec, sampler);
+        }
+
+        let (ggx, frame) = self.distribution(rec);
+        let wo = frame.to_local(-r_in.direction().unit());
+        if wo.z() <= 0.0 {
+            return None;
+        }
+
+        let choice = sampler.get_1d();
+        let sample = sampler.get_2d();
+        let coat_probability = self.coat_probability(wo);
+        let wo_inner = refract_in(wo, self.ir);
+        let (r_inner, rec_inner) = self.inner_hit(r_in, rec, frame, wo_inner);
+        let diffuseness = self.diffuseness(&r_inner, &rec_inner, frame, wo_inner);
+
+        let wi = if choice < coat_probability {
+            let h = ggx.sample_visible_normal(wo, sample);
+            let wi = (-wo).reflect(h);
+            if diffuseness.is_none() {
+                // The base only scatters in discrete directions, so each lobe stands alone.
+                if wi.z() <= 0.0 {
+                    return None;
+                }
+                let weight = ggx.g(wo, wi) / ggx.g1(wo) * fresnel_dielectric(wo.dot(h), self.ir)
+                    / coat_probability;
+                let scattered = Ray::new(rec.p(), frame.to_world(wi), r_in.time());
+                return Some((Colour(weight, weight, weight), scattered));
+            }
+            wi
+        } else {
+            let (attenuation, scattered) = self.base.scatter(&r_inner, &rec_inner, sampler)?;
+            let wi_inner = frame.to_local(scattered.direction().unit());
+            if wi_inner.z() <= 0.0 {
+                return None;
+            }
+            let wi = refract_out(wi_inner, self.ir)?;
+            if diffuseness.is_none() {
+                let fresnel = (1.0 - fresnel_dielectric(wo.z(), self.ir))
+                    * (1.0 - fresnel_dielectric(wi.z(), self.ir));
+                let weight = (fresnel / (1.0 - coat_probability))
+                    * self.attenuation(wo_inner.z().recip() + wi_inner.z().recip())
+                    * attenuation;
+                let scattered = Ray::new(rec.p(), frame.to_world(wi), r_in.time());
+                return Some((weight, scattered));
+            }
+            wi
+        };
+
+        // Weight the direction by the density of sampling it from either lobe.
+        let base = self.base.eval(
+            &r_inner,
+            &rec_inner,
+            frame.to_world(refract_in(wi, self.ir)),
+        );
+        let (value, pdf) = self.combine(ggx, wo, wi, base, diffuseness?)?;
+        let scattered = Ray::new(rec.p(), frame.to_world(wi), r_in.time());
+        Some((value / pdf, scattered))
+    }
+
+    fn eval(&self, r_in: &Ray, rec: &HitRecord, direction: Vec3) -> Option<(Colour, f64)> {
+        if !rec.front_face() {
+            return self.base.eval(r_in, rec, direction);
+        }
+
+        let (ggx, frame) = self.distribution(rec);
+        let wo = frame.to_local(-r_in.direction().unit());
+        let wi = frame.to_local(direction.unit());
+        if wo.z() <= 0.0 || wi.z() <= 0.0 {
+            return None;
+        }
+
+        // A base that only scatters in discrete directions can only be sampled.
+        let wo_inner = refract_in(wo, self.ir);
+        let (r_inner, rec_inner) = self.inner_hit(r_in, rec, frame, wo_inner);
+        let diffuseness = self.diffuseness(&r_inner, &rec_inner, frame, wo_inner)?;
+
+        let base = self.base.eval(
+            &r_inner,
+            &rec_inner,
+            frame.to_world(refract_in(wi, self.ir)),
+        );
+        self.combine(ggx, wo, wi, base, diffuseness)
+    }
+
+    fn emitted(&self, r_in: &Ray, rec: &HitRecord) -> Colour {
+        if !rec.front_face() {
+            return self.base.emitted(r_in, rec);
+        }
+
+        // Light from the base passes out through the coat.
+        let frame = Frame::new(rec.normal(), rec.tangent());
+        let wo = frame.to_local(-r_in.direction().unit());
+        if wo.z() <= 0.0 {
+            return Colour(0.0, 0.0, 0.0);
+        }
+        let wo_inner = refract_in(wo, self.ir);
+        let (r_inner, rec_inner) = self.inner_hit(r_in, rec, frame, wo_inner);
+        (1.0 - fresnel_dielectric(wo.z(), self.ir))
+            * self.attenuation(wo_inner.z().recip())
+            * self.base.emitted(&r_inner, &rec_inner)
+    }
+}
@@ -0,0 +1,260 @@
+use super::{
+    microfacet::{fresnel_conductor, Frame, Ggx},
+    Material,
+};
+use crate::{Colour, HitRecord, Ray, Sampler, SolidColour, Texture, Vec3};
+use std::sync::Arc;
+
+/**
+ * Type for representing the complex indices of refraction of some common metals, at the red,
+ * green and blue primaries.
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ConductorPreset {
+    Gold,
+    Copper,
+    Aluminium,
+    Silver,
+    Iron,
+}
+
+impl ConductorPreset {
+    /**
+     * Returns the real and imaginary parts of the index of refraction.
+     */
+    #[must_use]
+    pub const fn ior(self) -> (Colour, Colour) {
+        match self {
+            Self::Gold => (Colour(0.143, 0.374, 1.442), Colour(3.983, 2.385, 1.603)),
+            Self::Copper => (Colour(0.200, 0.924, 1.102), Colour(3.912, 2.452, 2.142)),
+            Self::Aluminium => (Colour(1.657, 0.880, 0.521), Colour(9.224, 6.270, 4.837)),
+            Self::Silver => (Colour(0.155, 0.117, 0.138), Colour(4.828, 3.122, 2.147)),
+            Self::Iron => (Colour(2.911, 2.950, 2.585), Colour(3.089, 2.932, 2.767)),
+        }
+    }
+}
+
+/**
+ * Type for representing a rough metal, as a surface of microscopic mirrors with normals in a GGX
+ * distribution.
+ *
+ * The reflectance follows from the complex index of refraction, so the colour of the metal and
+ * its brightening towards grazing angles come out naturally.  The roughness is in [0, 1]: 0 is a
+ * perfect mirror and 1 is very rough.  When it comes from a texture, the luminance of the texture
+ * is used.
+ *
+ * An anisotropic conductor, such as brushed metal, has different roughnesses along the surface
+ * tangent and bitangent, which are squared to give the widths αx and αy of the distribution.
+ * The tangent follows the texture coordinate `u`, turned about the normal by a rotation angle.
+ */
+#[derive(Debug)]
+pub struct Conductor {
+    eta: Colour,
+    k: Colour,
+    roughness_x: Arc<dyn Texture>,
+    roughness_y: Arc<dyn Texture>,
+    sin_rotation: f64,
+    cos_rotation: f64,
+}
+
+impl Conductor {
+    /**
+     * Creates a conductor with index of refraction `eta` + i`k` and a uniform `roughness`.
+     */
+    #[must_use]
+    pub fn new(eta: Colour, k: Colour, roughness: f64) -> Self {
+        Self::new_anisotropic(eta, k, roughness, roughness, 0.0)
+    }
+
+    #[must_use]
+    pub fn new_material(eta: Colour, k: Colour, roughness: f64) -> Arc<dyn Material> {
+        Arc::new(Self::new(eta, k, roughness))
+    }
+
+    /**
+     * Creates a conductor with index of refraction `eta` + i`k` and a roughness varying over the
+     * surface.
+     */
+    #[must_use]
+    pub fn new_textured(eta: Colour, k: Colour, roughness: impl Into<Arc<dyn Texture>>) -> Self {
+        let roughness = roughness.into();
+        Self::new_anisotropic_textured(eta, k, Arc::clone(&roughness), roughness, 0.0)
+    }
+
+    #[must_use]
+    pub fn new_material_textured(
+        eta: Colour,
+        k: Colour,
+        roughness: impl Into<Arc<dyn Texture>>,
+    ) -> Arc<dyn Material> {
+        Arc::new(Self::new_textured(eta, k, roughness))
+    }
+
+    /**
+     * Creates an anisotropic conductor with index of refraction `eta` + i`k`, uniform roughnesses
+     * `roughness_x` along the tangent and `roughness_y` along the bitangent, and the tangent
+     * turned by `rotation` degrees about the normal.
+     */
+    #[must_use]
+    pub fn new_anisotropic(
+        eta: Colour,
+        k: Colour,
+        roughness_x: f64,
+        roughness_y: f64,
+        rotation: f64,
+    ) -> Self {
+        Self::new_anisotropic_textured(
+            eta,
+            k,
+            SolidColour::new_texture(Colour(roughness_x, roughness_x, roughness_x)),
+            SolidColour::new_texture(Colour(roughness_y, roughness_y, roughness_y)),
+            rotation,
+        )
+    }
+
+    #[must_use]
+    pub fn new_material_anisotropic(
+        eta: Colour,
+        k: Colour,
+        roughness_x: f64,
+        roughness_y: f64,
+        rotation: f64,
+    ) -> Arc<dyn Material> {
+        Arc::new(Self::new_anisotropic(
+            eta,
+            k,
+            roughness_x,
+            roughness_y,
+            rotation,
+        ))
+    }
+
+    /**
+     * Creates an anisotropic conductor with index of refraction `eta` + i`k`, roughnesses
+     * `roughness_x` along the tangent and `roughness_y` along the bitangent varying over the
+     * surface, and the tangent turned by `rotation` degrees about the normal.
+     */
+    #[must_use]
+    pub fn new_anisotropic_textured(
+        eta: Colour,
+        k: Colour,
+        roughness_x: impl Into<Arc<dyn Texture>>,
+        roughness_y: impl Into<Arc<dyn Texture>>,
+        rotation: f64,
+    ) -> Self {
+        assert!(eta.0 > 0.0 && eta.1 > 0.0 && eta.2 > 0.0);
+        assert!(k.0 >= 0.0 && k.1 >= 0.0 && k.2 >= 0.0);
+        let (sin_rotation, cos_rotation) = rotation.to_radians().sin_cos();
+        Self {
+            eta,
+            k,
+            roughness_x: roughness_x.into(),
+            roughness_y: roughness_y.into(),
+            sin_rotation,
+            cos_rotation,
+        }
+    }
+
+    #[must_use]
+    pub fn new_material_anisotropic_textured(
+        eta: Colour,
+        k: Colour,
+        roughness_x: impl Into<Arc<dyn Texture>>,
+        roughness_y: impl Into<Arc<dyn Texture>>,
+        rotation: f64,
+    ) -> Arc<dyn Material> {
+        Arc::new(Self::new_anisotropic_textured(
+            eta,
+            k,
+            roughness_x,
+            roughness_y,
+            rotation,
+        ))
+    }
+
+    /**
+     * Creates one of the preset metals with a uniform `roughness`.
+     */
+    #[must_use]
+    pub fn new_preset(preset: ConductorPreset, roughness: f64) -> Self {
+        let (eta, k) = preset.ior();
+        Self::new(eta, k, roughness)
+    }
+
+    #[must_use]
+    pub fn new_material_preset(preset: ConductorPreset, roughness: f64) -> Arc<dyn Material> {
+        Arc::new(Self::new_preset(preset, roughness))
+    }
+
+    /**
+     * Returns the microfacet distribution and the local frame at `rec`.
+     */
+    #[must_use]
+    fn distribution(&self, rec: &HitRecord) -> (Ggx, Frame) {
+        let (u, v, p) = (rec.u(), rec.v(), rec.p());
+        let roughness_x = self.roughness_x.value(u, v, p).luminance();
+        let roughness_y = self.roughness_y.value(u, v, p).luminance();
+        let tangent = self.cos_rotation * rec.tangent() + self.sin_rotation * rec.bitangent();
+        (
+            Ggx::new(roughness_x, roughness_y),
+            Frame::new(rec.normal(), tangent),
+        )
+    }
+}
+
+impl Material for Conductor {
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        sampler: &mut dyn Sampler,
+    ) -> Option<(Colour, Ray)> {
+        let (ggx, frame) = self.distribution(rec);
+        let wo = frame.to_local(-r_in.direction().unit());
+        if wo.z() <= 0.0 {
+            return None;
+        }
+
+        let sample = sampler.get_2d();
+        if ggx.is_smooth() {
+            let attenuation = fresnel_conductor(wo.z(), self.eta, self.k);
+            let reflected = r_in.direction().unit().reflect(rec.normal());
+            return Some((attenuation, Ray::new(rec.p(), reflected, r_in.time())));
+        }
+
+        let h = ggx.sample_visible_normal(wo, sample);
+        let wi = (-wo).reflect(h);
+        if wi.z() <= 0.0 {
+            return None;
+        }
+
+        // The distribution and most of the masking cancel with the sampling density.
+        let attenuation =
+            (ggx.g(wo, wi) / ggx.g1(wo)) * fresnel_conductor(wo.dot(h), self.eta, self.k);
+        let scattered = Ray::new(rec.p(), frame.to_world(wi), r_in.time());
+        Some((attenuation, scattered))
+    }
+
+    fn eval(&self, r_in: &Ray, rec: &HitRecord, direction: Vec3) -> Option<(Colour, f64)> {
+        let (ggx, frame) = self.distribution(rec);
+        if ggx.is_smooth() {
+            return None;
+        }
+
+        let wo = frame.to_local(-r_in.direction().unit());
+        let wi = frame.to_local(direction.unit());
+        if wo.z() <= 0.0 || wi.z() <= 0.0 {
+            return None;
+        }
+
+        let h = (wo + wi).unit();
+        let pdf = ggx.visible_normal_pdf(wo, h) / (4.0 * wo.dot(h));
+        if pdf <= 0.0 {
+            return None;
+        }
+
+        let f = fresnel_conductor(wo.dot(h), self.eta, self.k);
+        let attenuation = (ggx.d(h) * ggx.g(wo, wi) / (4.0 * wo.z())) * f;
+        Some((attenuation, pdf))
+    }
+}
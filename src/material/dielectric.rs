@@ -1,36 +1,189 @@
 use super::Material;
-use crate::{Colour, HitRecord, Ray};
-use rand::prelude::*;
+use crate::{Colour, HitRecord, Ray, Sampler};
 use std::sync::Arc;
 
+/**
+ * Type for representing how the index of refraction of a transparent material varies with the
+ * wavelength of light, which splits white light into colours.
+ *
+ * Wavelengths are in micrometres in the formulae, as in published coefficients.  Rays carrying
+ * all colours at once use the index at the sodium d line, 587.6 nm, the wavelength at which
+ * indices of refraction are usually quoted.
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RefractiveIndex {
+    /**
+     * The same index at all wavelengths.
+     */
+    Constant(f64),
+
+    /**
+     * Cauchy’s equation, n = a + b / λ².
+     */
+    Cauchy { a: f64, b: f64 },
+
+    /**
+     * The Sellmeier equation, n² = 1 + Σ bᵢ λ² / (λ² − cᵢ).
+     */
+    Sellmeier { b: [f64; 3], c: [f64; 3] },
+}
+
+impl RefractiveIndex {
+    /**
+     * Schott N-BK7 borosilicate crown glass, used for most lenses and prisms.
+     */
+    pub const BK7: Self = Self::Sellmeier {
+        b: [1.039_612_12, 0.231_792_344, 1.010_469_45],
+        c: [0.006_000_698_67, 0.020_017_914_4, 103.560_653],
+    };
+
+    /**
+     * Fused silica (quartz glass).
+     */
+    pub const FUSED_SILICA: Self = Self::Sellmeier {
+        b: [0.696_166_3, 0.407_942_6, 0.897_479_4],
+        c: [0.004_679_148_26, 0.013_512_063_1, 97.934_002_5],
+    };
+
+    /**
+     * Dense flint glass (Schott N-SF11), strongly dispersive.
+     */
+    pub const SF11: Self = Self::Sellmeier {
+        b: [1.737_596_95, 0.313_747_346, 1.898_781_01],
+        c: [0.013_188_707, 0.062_306_814_2, 155.236_29],
+    };
+
+    /**
+     * Diamond, whose high dispersion gives it its “fire”.
+     */
+    pub const DIAMOND: Self = Self::Sellmeier {
+        b: [4.3356, 0.3306, 0.0],
+        c: [0.011_236, 0.030_625, 0.0],
+    };
+
+    /**
+     * Returns the index of refraction at `wavelength`, in nanometres, or at the sodium d line if
+     * `None`.
+     */
+    #[must_use]
+    pub fn at(self, wavelength: Option<f64>) -> f64 {
+        let lambda = wavelength.unwrap_or(587.6) * 1e-3;
+        let lambda2 = lambda * lambda;
+        match self {
+            Self::Constant(n) => n,
+            Self::Cauchy { a, b } => a + b / lambda2,
+            Self::Sellmeier { b, c } => (1.0
+                + (0..3)
+                    .map(|i| b[i] * lambda2 / (lambda2 - c[i]))
+                    .sum::<f64>())
+            .sqrt(),
+        }
+    }
+}
+
 /**
  * Type for representing a transparent material.
+ *
+ * Coloured glass absorbs light travelling through it, following the Beer–Lambert law: the
+ * fraction transmitted falls exponentially with the distance travelled inside.  Objects made of
+ * an absorbing dielectric must be closed, so that rays inside always leave through a back face.
+ *
+ * A dispersive dielectric, whose index of refraction depends on the wavelength, only separates
+ * colours when rendering spectrally.
  */
 #[derive(Debug)]
 pub struct Dielectric {
-    ir: f64, // Index of refraction.
+    ir: RefractiveIndex,
+    absorption: Colour,
 }
 
 impl Dielectric {
     #[must_use]
     pub fn new(ir: f64) -> Self {
-        Self { ir }
+        Self::new_absorbing(ir, Colour(0.0, 0.0, 0.0))
     }
 
     #[must_use]
     pub fn new_material(ir: f64) -> Arc<dyn Material> {
         Arc::new(Self::new(ir))
     }
+
+    /**
+     * Creates a dielectric with index of refraction `ir` and absorption coefficient `absorption`,
+     * per unit distance, for each colour component.
+     */
+    #[must_use]
+    pub fn new_absorbing(ir: f64, absorption: Colour) -> Self {
+        Self::new_dispersive(RefractiveIndex::Constant(ir), absorption)
+    }
+
+    #[must_use]
+    pub fn new_material_absorbing(ir: f64, absorption: Colour) -> Arc<dyn Material> {
+        Arc::new(Self::new_absorbing(ir, absorption))
+    }
+
+    /**
+     * Creates a dielectric with index of refraction `ir` that transmits the fraction
+     * `transmittance` of each colour component of the light travelling a distance `distance`
+     * through it.
+     */
+    #[must_use]
+    pub fn new_tinted(ir: f64, transmittance: Colour, distance: f64) -> Self {
+        assert!(distance > 0.0);
+        let absorption = |t: f64| {
+            assert!(t > 0.0 && t <= 1.0);
+            -t.ln() / distance
+        };
+        Self::new_absorbing(
+            ir,
+            Colour(
+                absorption(transmittance.0),
+                absorption(transmittance.1),
+                absorption(transmittance.2),
+            ),
+        )
+    }
+
+    #[must_use]
+    pub fn new_material_tinted(ir: f64, transmittance: Colour, distance: f64) -> Arc<dyn Material> {
+        Arc::new(Self::new_tinted(ir, transmittance, distance))
+    }
+
+    /**
+     * Creates a dielectric with an index of refraction `ir` varying with wavelength, and
+     * absorption coefficient `absorption`, per unit distance, for each colour component.
+     */
+    #[must_use]
+    pub fn new_dispersive(ir: RefractiveIndex, absorption: Colour) -> Self {
+        assert!(ir.at(None) > 0.0);
+        assert!(absorption.0 >= 0.0 && absorption.1 >= 0.0 && absorption.2 >= 0.0);
+        Self { ir, absorption }
+    }
+
+    #[must_use]
+    pub fn new_material_dispersive(ir: RefractiveIndex, absorption: Colour) -> Arc<dyn Material> {
+        Arc::new(Self::new_dispersive(ir, absorption))
+    }
+
+    /**
+     * Returns the absorption coefficient, per unit distance.
+     */
+    #[must_use]
+    pub const fn absorption(&self) -> Colour {
+        self.absorption
+    }
 }
 
 impl Material for Dielectric {
-    fn scatter(&self, r_in: &Ray, rec: &HitRecord) -> Option<(Colour, Ray)> {
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        sampler: &mut dyn Sampler,
+    ) -> Option<(Colour, Ray)> {
         let attenuation = Colour(1.0, 1.0, 1.0);
-        let refraction_ratio = if rec.front_face() {
-            self.ir.recip()
-        } else {
-            self.ir
-        };
+        let ir = self.ir.at(r_in.wavelength());
+        let refraction_ratio = if rec.front_face() { ir.recip() } else { ir };
 
         let unit_direction = r_in.direction().unit();
         let cos_theta = (-unit_direction).dot(rec.normal()).clamp(-1.0, 1.0);
@@ -38,7 +191,7 @@ impl Material for Dielectric {
 
         let cannot_refract = refraction_ratio * sin_theta > 1.0;
         let direction =
-            if cannot_refract || reflectance(cos_theta, refraction_ratio) > thread_rng().gen() {
+            if cannot_refract || reflectance(cos_theta, refraction_ratio) > sampler.get_1d() {
                 unit_direction.reflect(rec.normal())
             } else {
                 unit_direction.refract(rec.normal(), refraction_ratio)
@@ -48,6 +201,24 @@ impl Material for Dielectric {
 
         Some((attenuation, scattered))
     }
+
+    fn is_dispersive(&self) -> bool {
+        !matches!(self.ir, RefractiveIndex::Constant(_))
+    }
+
+    fn transmittance(&self, r_in: &Ray, rec: &HitRecord) -> Colour {
+        if rec.front_face() {
+            return Colour(1.0, 1.0, 1.0);
+        }
+
+        let distance = rec.t() * r_in.direction().length();
+        let Colour(r, g, b) = self.absorption;
+        Colour(
+            (-r * distance).exp(),
+            (-g * distance).exp(),
+            (-b * distance).exp(),
+        )
+    }
 }
 
 /**
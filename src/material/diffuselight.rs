@@ -1,33 +1,156 @@
 use super::Material;
-use crate::{Colour, HitRecord, Ray, Texture, Vec3};
-use std::sync::Arc;
+use crate::{
+    light::profile_intensity, Colour, HitRecord, IesProfile, Quaternion, Ray, Sampler, Texture,
+};
+use std::{f64::consts, sync::Arc};
+
+/**
+ * Smallest cosine between the normal and the emitted direction used to turn a photometric
+ * profile into radiance.
+ */
+const MIN_PROFILE_COSINE: f64 = 0.05;
 
 /**
  * Type for materials emitting difuse light.
+ *
+ * The emitted radiance is the value of the texture times a scalar intensity.  A one-sided light
+ * only emits from the front of the surface, the side its outward normal points towards.  A
+ * photometric profile can further vary the emission with direction, so that the light leaving
+ * the whole surface follows the profile, as if seen from far away.
  */
 #[derive(Debug)]
 pub struct DiffuseLight {
     emit: Arc<dyn Texture>,
+    intensity: f64,
+    two_sided: bool,
+    profile: Option<(Arc<IesProfile>, Quaternion)>,
 }
 
 impl DiffuseLight {
+    /**
+     * Creates a two-sided light emitting the radiance given by `emit`.
+     */
     #[must_use]
     pub fn new(emit: impl Into<Arc<dyn Texture>>) -> Self {
-        Self { emit: emit.into() }
+        Self::new_with_intensity(emit, 1.0, true)
     }
 
     #[must_use]
     pub fn new_material(emit: impl Into<Arc<dyn Texture>>) -> Arc<dyn Material> {
         Arc::new(Self::new(emit))
     }
+
+    /**
+     * Creates a light emitting the radiance given by `emit` times `intensity`, from the front of
+     * the surface only, or from both sides if `two_sided` is `true`.
+     */
+    #[must_use]
+    pub fn new_with_intensity(
+        emit: impl Into<Arc<dyn Texture>>,
+        intensity: f64,
+        two_sided: bool,
+    ) -> Self {
+        assert!(intensity >= 0.0);
+        Self {
+            emit: emit.into(),
+            intensity,
+            two_sided,
+            profile: None,
+        }
+    }
+
+    #[must_use]
+    pub fn new_material_with_intensity(
+        emit: impl Into<Arc<dyn Texture>>,
+        intensity: f64,
+        two_sided: bool,
+    ) -> Arc<dyn Material> {
+        Arc::new(Self::new_with_intensity(emit, intensity, two_sided))
+    }
+
+    /**
+     * Creates a light emitting a total power of `power` watts, spread evenly over a surface of
+     * area `area`, in square metres (scene units).  A texture value of 1 corresponds to this
+     * power; `emit` tints the light and can vary over the surface.
+     */
+    #[must_use]
+    pub fn new_with_power(
+        emit: impl Into<Arc<dyn Texture>>,
+        power: f64,
+        area: f64,
+        two_sided: bool,
+    ) -> Self {
+        assert!(power >= 0.0);
+        assert!(area > 0.0);
+
+        // A Lambertian emitter of radiance L emits πL watts per square metre from each side.
+        let sides = if two_sided { 2.0 } else { 1.0 };
+        let intensity = power / (consts::PI * area * sides);
+        Self::new_with_intensity(emit, intensity, two_sided)
+    }
+
+    #[must_use]
+    pub fn new_material_with_power(
+        emit: impl Into<Arc<dyn Texture>>,
+        power: f64,
+        area: f64,
+        two_sided: bool,
+    ) -> Arc<dyn Material> {
+        Arc::new(Self::new_with_power(emit, power, area, two_sided))
+    }
+
+    /**
+     * Returns the scalar intensity multiplying the texture, as a radiance in W/(sr·m²).
+     */
+    #[must_use]
+    pub const fn intensity(&self) -> f64 {
+        self.intensity
+    }
+
+    /**
+     * Modulates the emitted radiance by the luminous intensity distribution `profile`, relative to
+     * its brightest direction.  `rotation` turns the profile’s frame, whose nadir is −y, into the
+     * scene, so the same orientation applies at every point of the surface.
+     */
+    pub fn set_profile(&mut self, profile: Arc<IesProfile>, rotation: Quaternion) {
+        self.profile = Some((profile, rotation));
+    }
+
+    #[must_use]
+    pub const fn two_sided(&self) -> bool {
+        self.two_sided
+    }
 }
 
 impl Material for DiffuseLight {
-    fn scatter(&self, _r_in: &Ray, _rec: &HitRecord) -> Option<(Colour, Ray)> {
+    fn scatter(
+        &self,
+        _r_in: &Ray,
+        _rec: &HitRecord,
+        _sampler: &mut dyn Sampler,
+    ) -> Option<(Colour, Ray)> {
         None
     }
 
-    fn emitted(&self, u: f64, v: f64, p: Vec3) -> Colour {
-        self.emit.value(u, v, p)
+    fn scatters(&self) -> bool {
+        false
+    }
+
+    fn emitted(&self, r_in: &Ray, rec: &HitRecord) -> Colour {
+        if !self.two_sided && !rec.front_face() {
+            return Colour(0.0, 0.0, 0.0);
+        }
+
+        // A profile gives the intensity of the whole emitter, which is the radiance times the
+        // projected area, so the radiance is divided by the cosine to follow it.  The cosine is
+        // clamped to keep the radiance finite at grazing angles.
+        let profile = if self.profile.is_some() {
+            let cosine = r_in.direction().unit().dot(rec.normal()).abs();
+            profile_intensity(self.profile.as_ref(), -r_in.direction())
+                / cosine.max(MIN_PROFILE_COSINE)
+        } else {
+            1.0
+        };
+        (self.intensity * profile) * self.emit.value(rec.u(), rec.v(), rec.p())
     }
 }
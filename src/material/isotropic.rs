@@ -1,6 +1,6 @@
 use super::Material;
-use crate::{Colour, HitRecord, Ray, Texture, Vec3};
-use std::sync::Arc;
+use crate::{Colour, HitRecord, Ray, Sampler, Texture, Vec3};
+use std::{f64::consts, sync::Arc};
 
 /**
  * Type for materials that scatter randomly.
@@ -25,9 +25,21 @@ impl Isotropic {
 }
 
 impl Material for Isotropic {
-    fn scatter(&self, r_in: &Ray, rec: &HitRecord) -> Option<(Colour, Ray)> {
-        let scattered = Ray::new(rec.p(), Vec3::new_random_in_unit_sphere(), r_in.time());
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        sampler: &mut dyn Sampler,
+    ) -> Option<(Colour, Ray)> {
+        let direction = Vec3::new_unit_from_sample(sampler.get_2d());
+        let scattered = Ray::new(rec.p(), direction, r_in.time());
         let attenuation = self.albedo.value(rec.u(), rec.v(), rec.p());
         Some((attenuation, scattered))
     }
+
+    fn eval(&self, _r_in: &Ray, rec: &HitRecord, _direction: Vec3) -> Option<(Colour, f64)> {
+        let pdf = 0.25 * consts::FRAC_1_PI;
+        let attenuation = self.albedo.value(rec.u(), rec.v(), rec.p());
+        Some((pdf * attenuation, pdf))
+    }
 }
@@ -1,6 +1,6 @@
 use super::Material;
-use crate::{Colour, HitRecord, Ray, Texture, Vec3};
-use std::sync::Arc;
+use crate::{Colour, HitRecord, Ray, Sampler, Texture, Vec3};
+use std::{f64::consts, sync::Arc};
 
 /**
  * Type for representing a pre-Lambertian scattering material.
@@ -69,8 +69,17 @@ impl Lambertian2 {
 }
 
 impl Material for Lambertian0 {
-    fn scatter(&self, r_in: &Ray, rec: &HitRecord) -> Option<(Colour, Ray)> {
-        let mut scatter_direction = Vec3::new_random_in_hemisphere(rec.normal());
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        sampler: &mut dyn Sampler,
+    ) -> Option<(Colour, Ray)> {
+        let mut scatter_direction = Vec3::new_unit_from_sample(sampler.get_2d());
+        if scatter_direction.dot(rec.normal()) < 0.0 {
+            // Flip into the same hemisphere as the normal.
+            scatter_direction = -scatter_direction;
+        }
 
         // Catch degenerate scatter direction.
         if scatter_direction.is_near_zero() {
@@ -82,11 +91,29 @@ impl Material for Lambertian0 {
 
         Some((attenuation, scattered))
     }
+
+    fn eval(&self, _r_in: &Ray, rec: &HitRecord, direction: Vec3) -> Option<(Colour, f64)> {
+        // Directions are sampled uniformly over the hemisphere.
+        if direction.dot(rec.normal()) <= 0.0 {
+            return None;
+        }
+
+        let pdf = 0.5 * consts::FRAC_1_PI;
+        let attenuation = self.albedo.value(rec.u(), rec.v(), rec.p());
+        Some((pdf * attenuation, pdf))
+    }
 }
 
 impl Material for Lambertian1 {
-    fn scatter(&self, r_in: &Ray, rec: &HitRecord) -> Option<(Colour, Ray)> {
-        let mut scatter_direction = rec.normal() + Vec3::new_random_in_unit_sphere();
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        sampler: &mut dyn Sampler,
+    ) -> Option<(Colour, Ray)> {
+        let sample = sampler.get_2d();
+        let mut scatter_direction =
+            rec.normal() + Vec3::new_in_unit_sphere_from_sample(sample, sampler.get_1d());
 
         // Catch degenerate scatter direction.
         if scatter_direction.is_near_zero() {
@@ -101,8 +128,13 @@ impl Material for Lambertian1 {
 }
 
 impl Material for Lambertian2 {
-    fn scatter(&self, r_in: &Ray, rec: &HitRecord) -> Option<(Colour, Ray)> {
-        let mut scatter_direction = rec.normal() + Vec3::new_random_unit();
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        sampler: &mut dyn Sampler,
+    ) -> Option<(Colour, Ray)> {
+        let mut scatter_direction = rec.normal() + Vec3::new_unit_from_sample(sampler.get_2d());
 
         // Catch degenerate scatter direction.
         if scatter_direction.is_near_zero() {
@@ -114,4 +146,16 @@ impl Material for Lambertian2 {
 
         Some((attenuation, scattered))
     }
+
+    fn eval(&self, _r_in: &Ray, rec: &HitRecord, direction: Vec3) -> Option<(Colour, f64)> {
+        // Directions are sampled in proportion to the cosine, which cancels out the BSDF.
+        let cosine = direction.unit().dot(rec.normal());
+        if cosine <= 0.0 {
+            return None;
+        }
+
+        let pdf = cosine * consts::FRAC_1_PI;
+        let attenuation = self.albedo.value(rec.u(), rec.v(), rec.p());
+        Some((pdf * attenuation, pdf))
+    }
 }
@@ -1,5 +1,5 @@
 use super::Material;
-use crate::{Colour, HitRecord, Ray, Texture, Vec3};
+use crate::{Colour, HitRecord, Ray, Sampler, Texture, Vec3};
 use std::sync::Arc;
 
 /**
@@ -29,14 +29,17 @@ impl Metal {
 }
 
 impl Material for Metal {
-    fn scatter(&self, r_in: &Ray, rec: &HitRecord) -> Option<(Colour, Ray)> {
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        sampler: &mut dyn Sampler,
+    ) -> Option<(Colour, Ray)> {
         let reflected = r_in.direction().unit().reflect(rec.normal());
         let attenuation = self.albedo.value(rec.u(), rec.v(), rec.p());
-        let scattered = Ray::new(
-            rec.p(),
-            reflected + self.fuzz * Vec3::new_random_in_unit_sphere(),
-            r_in.time(),
-        );
+        let sample = sampler.get_2d();
+        let fuzz = Vec3::new_in_unit_sphere_from_sample(sample, sampler.get_1d());
+        let scattered = Ray::new(rec.p(), reflected + self.fuzz * fuzz, r_in.time());
 
         if scattered.direction().dot(rec.normal()) > 0.0 {
             Some((attenuation, scattered))
@@ -0,0 +1,237 @@
+use crate::{Colour, Vec3};
+use std::f64::consts;
+
+/**
+ * Roughness below which a microfacet surface is treated as perfectly smooth.
+ */
+const SMOOTH_ALPHA: f64 = 1e-3;
+
+/**
+ * Smallest width of the distribution along either axis, which keeps the sharper axis of a very
+ * anisotropic distribution finite.
+ */
+const MIN_ALPHA: f64 = 1e-4;
+
+/**
+ * Smallest roughness of microfacet lobes that are mixed with other lobes.  Keeping them very
+ * slightly rough lets every lobe be evaluated, so that they can be sampled together.
+ */
+pub(crate) const MIN_ROUGHNESS: f64 = 0.04;
+
+/**
+ * Type for an orthonormal basis, used to express directions relative to a surface normal (the
+ * local z axis).
+ */
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Frame {
+    s: Vec3,
+    t: Vec3,
+    n: Vec3,
+}
+
+impl Frame {
+    /**
+     * Creates the frame with unit normal `n` and the perpendicular unit tangent `s` as its x axis.
+     */
+    #[must_use]
+    pub(crate) fn new(n: Vec3, s: Vec3) -> Self {
+        Self {
+            s,
+            t: n.cross(s),
+            n,
+        }
+    }
+
+    #[must_use]
+    pub(crate) fn to_local(self, v: Vec3) -> Vec3 {
+        Vec3(v.dot(self.s), v.dot(self.t), v.dot(self.n))
+    }
+
+    #[must_use]
+    pub(crate) fn to_world(self, v: Vec3) -> Vec3 {
+        v.x() * self.s + v.y() * self.t + v.z() * self.n
+    }
+}
+
+/**
+ * Type for the GGX (Trowbridge–Reitz) distribution of microfacet normals, with Smith’s
+ * height-correlated masking–shadowing function.  Directions are in the local frame of the
+ * surface, with the normal along +z.
+ */
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Ggx {
+    alpha_x: f64,
+    alpha_y: f64,
+}
+
+impl Ggx {
+    /**
+     * Creates the distribution for perceptual roughnesses in [0, 1] along the x and y axes, which
+     * are squared to give the widths of the distribution.
+     */
+    #[must_use]
+    pub(crate) fn new(roughness_x: f64, roughness_y: f64) -> Self {
+        Self {
+            alpha_x: roughness_x.clamp(0.0, 1.0).powi(2).max(MIN_ALPHA),
+            alpha_y: roughness_y.clamp(0.0, 1.0).powi(2).max(MIN_ALPHA),
+        }
+    }
+
+    /**
+     * Returns `true` if the distribution is too narrow to sample or evaluate, and the surface
+     * should be treated as a smooth mirror.
+     */
+    #[must_use]
+    pub(crate) fn is_smooth(self) -> bool {
+        self.alpha_x.max(self.alpha_y) < SMOOTH_ALPHA
+    }
+
+    /**
+     * Returns the density of microfacet normals `h`, per unit projected area.
+     */
+    #[must_use]
+    pub(crate) fn d(self, h: Vec3) -> f64 {
+        if h.z() <= 0.0 {
+            return 0.0;
+        }
+
+        let x = h.x() / self.alpha_x;
+        let y = h.y() / self.alpha_y;
+        let e = x * x + y * y + h.z() * h.z();
+        1.0 / (consts::PI * self.alpha_x * self.alpha_y * e * e)
+    }
+
+    /**
+     * Returns Smith’s auxiliary function Λ for direction `w`.
+     */
+    #[must_use]
+    fn lambda(self, w: Vec3) -> f64 {
+        let z2 = w.z() * w.z();
+        if z2 <= 0.0 {
+            return f64::INFINITY;
+        }
+
+        let a2 = (self.alpha_x * w.x()).powi(2) + (self.alpha_y * w.y()).powi(2);
+        0.5 * ((1.0 + a2 / z2).sqrt() - 1.0)
+    }
+
+    /**
+     * Returns the fraction of microfacets visible from direction `w`.
+     */
+    #[must_use]
+    pub(crate) fn g1(self, w: Vec3) -> f64 {
+        1.0 / (1.0 + self.lambda(w))
+    }
+
+    /**
+     * Returns the fraction of microfacets visible from both `wo` and `wi`.
+     */
+    #[must_use]
+    pub(crate) fn g(self, wo: Vec3, wi: Vec3) -> f64 {
+        1.0 / (1.0 + self.lambda(wo) + self.lambda(wi))
+    }
+
+    /**
+     * Samples a microfacet normal from the distribution of normals visible from `wo`, which must
+     * be above the surface (Heitz’s method).
+     */
+    #[must_use]
+    pub(crate) fn sample_visible_normal(self, wo: Vec3, sample: (f64, f64)) -> Vec3 {
+        // Stretch the view direction so the distribution becomes a hemisphere.
+        let v = Vec3(self.alpha_x * wo.x(), self.alpha_y * wo.y(), wo.z()).unit();
+
+        let length_squared = v.x() * v.x() + v.y() * v.y();
+        let t1 = if length_squared > 0.0 {
+            Vec3(-v.y(), v.x(), 0.0) / length_squared.sqrt()
+        } else {
+            Vec3(1.0, 0.0, 0.0)
+        };
+        let t2 = v.cross(t1);
+
+        // Sample the projected hemisphere: a disk, partly foreshortened.
+        let r = sample.0.sqrt();
+        let (sin_phi, cos_phi) = (2.0 * consts::PI * sample.1).sin_cos();
+        let p1 = r * cos_phi;
+        let s = 0.5 * (1.0 + v.z());
+        let p2 = (1.0 - s) * (1.0 - p1 * p1).max(0.0).sqrt() + s * r * sin_phi;
+        let h = p1 * t1 + p2 * t2 + (1.0 - p1 * p1 - p2 * p2).max(0.0).sqrt() * v;
+
+        // Unstretch.
+        Vec3(self.alpha_x * h.x(), self.alpha_y * h.y(), h.z().max(0.0)).unit()
+    }
+
+    /**
+     * Returns the probability density, per unit solid angle, of `sample_visible_normal` choosing
+     * the normal `h` for direction `wo`.
+     */
+    #[must_use]
+    pub(crate) fn visible_normal_pdf(self, wo: Vec3, h: Vec3) -> f64 {
+        if wo.z() <= 0.0 {
+            return 0.0;
+        }
+
+        self.g1(wo) * wo.dot(h).max(0.0) * self.d(h) / wo.z()
+    }
+}
+
+/**
+ * Returns the Fresnel reflectance of a conductor with complex index of refraction `eta` + i`k`,
+ * for light arriving at an angle with cosine `cos_theta` to the normal.
+ */
+#[must_use]
+pub(crate) fn fresnel_conductor(cos_theta: f64, eta: Colour, k: Colour) -> Colour {
+    let f = |eta: f64, k: f64| {
+        let cos2 = cos_theta.clamp(0.0, 1.0).powi(2);
+        let sin2 = 1.0 - cos2;
+        let t0 = eta * eta - k * k - sin2;
+        let a2_plus_b2 = (t0 * t0 + 4.0 * eta * eta * k * k).sqrt();
+        let a = (0.5 * (a2_plus_b2 + t0)).max(0.0).sqrt();
+
+        let t1 = a2_plus_b2 + cos2;
+        let t2 = 2.0 * cos_theta.clamp(0.0, 1.0) * a;
+        let rs = (t1 - t2) / (t1 + t2);
+
+        let t3 = cos2 * a2_plus_b2 + sin2 * sin2;
+        let t4 = t2 * sin2;
+        let rp = rs * (t3 - t4) / (t3 + t4);
+        0.5 * (rs + rp)
+    };
+    Colour(f(eta.0, k.0), f(eta.1, k.1), f(eta.2, k.2))
+}
+
+/**
+ * Returns the Fresnel reflectance of unpolarised light at an interface between dielectrics, for
+ * light arriving at an angle with cosine `cos_theta` to the normal, where `eta` is the ratio of
+ * the index of refraction on the far side to that on the near side.  Returns 1 for total internal
+ * reflection.
+ */
+#[must_use]
+pub(crate) fn fresnel_dielectric(cos_theta: f64, eta: f64) -> f64 {
+    let cos_i = cos_theta.clamp(0.0, 1.0);
+    let sin2_t = (1.0 - cos_i * cos_i) / (eta * eta);
+    if sin2_t >= 1.0 {
+        return 1.0;
+    }
+
+    let cos_t = (1.0 - sin2_t).sqrt();
+    let r_parallel = (eta * cos_i - cos_t) / (eta * cos_i + cos_t);
+    let r_perpendicular = (cos_i - eta * cos_t) / (cos_i + eta * cos_t);
+    0.5 * (r_parallel * r_parallel + r_perpendicular * r_perpendicular)
+}
+
+/**
+ * Refracts the direction `wo`, pointing away from the surface on the side of `h`, through the
+ * microfacet with unit normal `h`, where `eta` is the ratio of the index of refraction on the far
+ * side to that on the near side.  Returns `None` for total internal reflection.
+ */
+#[must_use]
+pub(crate) fn refract(wo: Vec3, h: Vec3, eta: f64) -> Option<Vec3> {
+    let cos_i = wo.dot(h);
+    let sin2_t = (1.0 - cos_i * cos_i).max(0.0) / (eta * eta);
+    if sin2_t >= 1.0 {
+        return None;
+    }
+
+    let cos_t = (1.0 - sin2_t).sqrt();
+    Some(-wo / eta + (cos_i / eta - cos_t) * h)
+}
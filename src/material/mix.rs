@@ -0,0 +1,224 @@
+use super::Material;
+use crate::{
+    sampler::{mix_bits, to_unit_f64},
+    Colour, HitRecord, Ray, Sampler, SolidColour, Texture, Vec3,
+};
+use std::sync::Arc;
+
+/**
+ * Type for a blend of two materials, such as rust over metal.  At each hit one of them is chosen
+ * at random, the second with probability given by the amount, so that on average the light they
+ * scatter and emit is blended.  When the amount comes from a texture, its luminance is used, as a
+ * mask from the first material (0) to the second (1).
+ *
+ * The choice is made from a hash of the hit point and the incoming direction, so that sampling a
+ * direction and evaluating one at the same hit always see the same material.
+ */
+#[derive(Debug)]
+pub struct Mix {
+    first: Arc<dyn Material>,
+    second: Arc<dyn Material>,
+    amount: Arc<dyn Texture>,
+}
+
+/**
+ * Type for the sum of two materials, such as an emissive pattern on top of a reflective surface.
+ * The light they emit and the light they scatter are both added.
+ *
+ * Scattering picks either of the materials that scatter light with equal probability, so a
+ * material that only emits light, like `DiffuseLight`, costs nothing.
+ */
+#[derive(Debug)]
+pub struct AddMaterial {
+    first: Arc<dyn Material>,
+    second: Arc<dyn Material>,
+}
+
+impl Mix {
+    /**
+     * Creates a blend of `first` and `second`, with a fraction `amount` of the second.
+     */
+    #[must_use]
+    pub fn new(first: Arc<dyn Material>, second: Arc<dyn Material>, amount: f64) -> Self {
+        assert!((0.0..=1.0).contains(&amount));
+        let amount = SolidColour::new_texture(Colour(amount, amount, amount));
+        Self::new_textured(first, second, amount)
+    }
+
+    #[must_use]
+    pub fn new_material(
+        first: Arc<dyn Material>,
+        second: Arc<dyn Material>,
+        amount: f64,
+    ) -> Arc<dyn Material> {
+        Arc::new(Self::new(first, second, amount))
+    }
+
+    /**
+     * Creates a blend of `first` and `second`, with the fraction of the second varying over the
+     * surface.
+     */
+    #[must_use]
+    pub fn new_textured(
+        first: Arc<dyn Material>,
+        second: Arc<dyn Material>,
+        amount: impl Into<Arc<dyn Texture>>,
+    ) -> Self {
+        Self {
+            first,
+            second,
+            amount: amount.into(),
+        }
+    }
+
+    #[must_use]
+    pub fn new_material_textured(
+        first: Arc<dyn Material>,
+        second: Arc<dyn Material>,
+        amount: impl Into<Arc<dyn Texture>>,
+    ) -> Arc<dyn Material> {
+        Arc::new(Self::new_textured(first, second, amount))
+    }
+
+    /**
+     * Returns the material chosen for the hit `rec` by the incoming ray `r_in`.
+     */
+    #[must_use]
+    fn choose(&self, r_in: &Ray, rec: &HitRecord) -> &dyn Material {
+        let amount = self.amount.value(rec.u(), rec.v(), rec.p()).luminance();
+        if hash(rec.p(), r_in.direction()) < amount {
+            self.second.as_ref()
+        } else {
+            self.first.as_ref()
+        }
+    }
+}
+
+impl AddMaterial {
+    /**
+     * Creates the sum of `first` and `second`.
+     */
+    #[must_use]
+    pub fn new(first: Arc<dyn Material>, second: Arc<dyn Material>) -> Self {
+        Self { first, second }
+    }
+
+    #[must_use]
+    pub fn new_material(first: Arc<dyn Material>, second: Arc<dyn Material>) -> Arc<dyn Material> {
+        Arc::new(Self::new(first, second))
+    }
+}
+
+/**
+ * Returns a number in [0, 1) hashed from the position `p` and the direction `direction`.
+ */
+#[must_use]
+fn hash(p: Vec3, direction: Vec3) -> f64 {
+    let values = [
+        p.x(),
+        p.y(),
+        p.z(),
+        direction.x(),
+        direction.y(),
+        direction.z(),
+    ];
+    let hash = values
+        .iter()
+        .fold(0, |hash, value| mix_bits(hash ^ value.to_bits()));
+    to_unit_f64(hash)
+}
+
+impl Material for Mix {
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        sampler: &mut dyn Sampler,
+    ) -> Option<(Colour, Ray)> {
+        self.choose(r_in, rec).scatter(r_in, rec, sampler)
+    }
+
+    fn eval(&self, r_in: &Ray, rec: &HitRecord, direction: Vec3) -> Option<(Colour, f64)> {
+        self.choose(r_in, rec).eval(r_in, rec, direction)
+    }
+
+    fn scatters(&self) -> bool {
+        self.first.scatters() || self.second.scatters()
+    }
+
+    fn is_dispersive(&self) -> bool {
+        self.first.is_dispersive() || self.second.is_dispersive()
+    }
+
+    fn transmittance(&self, r_in: &Ray, rec: &HitRecord) -> Colour {
+        self.choose(r_in, rec).transmittance(r_in, rec)
+    }
+
+    fn emitted(&self, r_in: &Ray, rec: &HitRecord) -> Colour {
+        self.choose(r_in, rec).emitted(r_in, rec)
+    }
+}
+
+impl Material for AddMaterial {
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        sampler: &mut dyn Sampler,
+    ) -> Option<(Colour, Ray)> {
+        let (chosen, probability) = match (self.first.scatters(), self.second.scatters()) {
+            (true, true) if sampler.get_1d() < 0.5 => (&self.first, 0.5),
+            (true, true) => (&self.second, 0.5),
+            (true, false) => (&self.first, 1.0),
+            (false, true) => (&self.second, 1.0),
+            (false, false) => return None,
+        };
+        let (attenuation, scattered) = chosen.scatter(r_in, rec, sampler)?;
+
+        // Weight a direction both materials can be evaluated in by the density of sampling it
+        // from either, as for lighting sampled directly.
+        match self.eval(r_in, rec, scattered.direction()) {
+            Some((value, pdf)) if pdf > 0.0 => Some((value / pdf, scattered)),
+            _ => Some((attenuation / probability, scattered)),
+        }
+    }
+
+    fn eval(&self, r_in: &Ray, rec: &HitRecord, direction: Vec3) -> Option<(Colour, f64)> {
+        // Lighting can only be sampled directly if every material that scatters can be
+        // evaluated, as a direction scattered by the other could not be weighted against it.
+        let scattering = [&self.first, &self.second]
+            .iter()
+            .filter(|material| material.scatters())
+            .count();
+        let mut value = Colour(0.0, 0.0, 0.0);
+        let mut pdf = 0.0;
+        for material in [&self.first, &self.second].iter() {
+            if material.scatters() {
+                let (material_value, material_pdf) = material.eval(r_in, rec, direction)?;
+                value += material_value;
+                pdf += material_pdf / scattering as f64;
+            }
+        }
+        if scattering > 0 {
+            Some((value, pdf))
+        } else {
+            None
+        }
+    }
+
+    fn scatters(&self) -> bool {
+        self.first.scatters() || self.second.scatters()
+    }
+
+    fn is_dispersive(&self) -> bool {
+        self.first.is_dispersive() || self.second.is_dispersive()
+    }
+
+    fn transmittance(&self, r_in: &Ray, rec: &HitRecord) -> Colour {
+        self.first.transmittance(r_in, rec) * self.second.transmittance(r_in, rec)
+    }
+
+    fn emitted(&self, r_in: &Ray, rec: &HitRecord) -> Colour {
+        self.first.emitted(r_in, rec) + self.second.emitted(r_in, rec)
+    }
+}
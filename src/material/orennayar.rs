@@ -0,0 +1,139 @@
+use super::Material;
+use crate::{Colour, HitRecord, Ray, Sampler, SolidColour, Texture, Vec3};
+use std::{f64::consts, sync::Arc};
+
+/**
+ * Type for representing a rough diffuse material, such as clay, concrete or the surface of the
+ * moon, which look flatter than a Lambertian surface and brighter towards the light (Oren and
+ * Nayar, “Generalization of Lambert’s Reflectance Model”, 1994, in its qualitative form).
+ *
+ * The surface is made of tiny Lambertian facets whose slopes have a standard deviation of
+ * `sigma` degrees.  With `sigma` 0 it is the same as `Lambertian2`.  When `sigma` comes from a
+ * texture, its luminance is the angle in degrees.
+ */
+#[derive(Debug)]
+pub struct OrenNayar {
+    albedo: Arc<dyn Texture>,
+    sigma: Arc<dyn Texture>,
+}
+
+impl OrenNayar {
+    /**
+     * Creates a rough diffuse material with `albedo`, and a uniform standard deviation of the
+     * slopes of its facets of `sigma` degrees.
+     */
+    #[must_use]
+    pub fn new(albedo: impl Into<Arc<dyn Texture>>, sigma: f64) -> Self {
+        assert!(sigma >= 0.0);
+        Self::new_textured(
+            albedo,
+            SolidColour::new_texture(Colour(sigma, sigma, sigma)),
+        )
+    }
+
+    #[must_use]
+    pub fn new_material(albedo: impl Into<Arc<dyn Texture>>, sigma: f64) -> Arc<dyn Material> {
+        Arc::new(Self::new(albedo, sigma))
+    }
+
+    /**
+     * Creates a rough diffuse material with `albedo`, and the standard deviation of the slopes of
+     * its facets, in degrees, varying over the surface.
+     */
+    #[must_use]
+    pub fn new_textured(
+        albedo: impl Into<Arc<dyn Texture>>,
+        sigma: impl Into<Arc<dyn Texture>>,
+    ) -> Self {
+        Self {
+            albedo: albedo.into(),
+            sigma: sigma.into(),
+        }
+    }
+
+    #[must_use]
+    pub fn new_material_textured(
+        albedo: impl Into<Arc<dyn Texture>>,
+        sigma: impl Into<Arc<dyn Texture>>,
+    ) -> Arc<dyn Material> {
+        Arc::new(Self::new_textured(albedo, sigma))
+    }
+
+    /**
+     * Returns the factor by which the roughness scales the Lambertian reflection at `rec`, of
+     * light arriving from the unit vector `wi` and leaving towards the unit vector `wo`.
+     */
+    #[must_use]
+    fn roughness_factor(&self, rec: &HitRecord, wo: Vec3, wi: Vec3) -> f64 {
+        let sigma = self
+            .sigma
+            .value(rec.u(), rec.v(), rec.p())
+            .luminance()
+            .max(0.0)
+            .to_radians();
+        let sigma2 = sigma * sigma;
+        let a = 1.0 - sigma2 / (2.0 * (sigma2 + 0.33));
+        let b = 0.45 * sigma2 / (sigma2 + 0.09);
+
+        let n = rec.normal();
+        let cos_o = wo.dot(n).clamp(0.0, 1.0);
+        let cos_i = wi.dot(n).clamp(0.0, 1.0);
+        let sin_o = (1.0 - cos_o * cos_o).sqrt();
+        let sin_i = (1.0 - cos_i * cos_i).sqrt();
+
+        // Cosine of the difference in azimuth between the directions.
+        let tangent_o = wo - cos_o * n;
+        let tangent_i = wi - cos_i * n;
+        let cos_phi = if sin_o > 1e-4 && sin_i > 1e-4 {
+            (tangent_o.dot(tangent_i) / (sin_o * sin_i)).max(0.0)
+        } else {
+            0.0
+        };
+
+        // The sine of the larger angle from the normal, and the tangent of the smaller one.
+        let (sin_alpha, tan_beta) = if cos_i > cos_o {
+            (sin_o, sin_i / cos_i)
+        } else {
+            (sin_i, sin_o / cos_o.max(1e-8))
+        };
+
+        a + b * cos_phi * sin_alpha * tan_beta
+    }
+}
+
+impl Material for OrenNayar {
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        sampler: &mut dyn Sampler,
+    ) -> Option<(Colour, Ray)> {
+        let mut scatter_direction = rec.normal() + Vec3::new_unit_from_sample(sampler.get_2d());
+
+        // Catch degenerate scatter direction.
+        if scatter_direction.is_near_zero() {
+            scatter_direction = rec.normal();
+        }
+
+        // Directions are sampled in proportion to the cosine, as for a Lambertian surface, leaving
+        // the roughness to weight them.
+        let factor = self.roughness_factor(rec, -r_in.direction().unit(), scatter_direction.unit());
+        let attenuation = factor * self.albedo.value(rec.u(), rec.v(), rec.p());
+        let scattered = Ray::new(rec.p(), scatter_direction, r_in.time());
+
+        Some((attenuation, scattered))
+    }
+
+    fn eval(&self, r_in: &Ray, rec: &HitRecord, direction: Vec3) -> Option<(Colour, f64)> {
+        let wi = direction.unit();
+        let cosine = wi.dot(rec.normal());
+        if cosine <= 0.0 {
+            return None;
+        }
+
+        let pdf = cosine * consts::FRAC_1_PI;
+        let factor = self.roughness_factor(rec, -r_in.direction().unit(), wi);
+        let attenuation = self.albedo.value(rec.u(), rec.v(), rec.p());
+        Some(((pdf * factor) * attenuation, pdf))
+    }
+}
@@ -0,0 +1,412 @@
+use super::{
+    microfacet::{fresnel_dielectric, refract, Frame, Ggx, MIN_ROUGHNESS},
+    Material,
+};
+use crate::{Colour, HitRecord, Ray, Sampler, SolidColour, Texture, Vec3};
+use std::{f64::consts, sync::Arc};
+
+/**
+ * Roughness of the clear coat.
+ */
+const CLEARCOAT_ROUGHNESS: f64 = 0.05;
+
+/**
+ * Type for representing a principled material, described by the parameters artists use rather
+ * than by a physical model (after Burley’s Disney BSDF).
+ *
+ * * `base_colour` is the colour of the diffuse surface, of the reflections of a metal, or of the
+ *   light transmitted by glass.
+ * * `metallic` blends from a dielectric (0) to a metal (1), whose reflectance tends from the base
+ *   colour to white at grazing angles.
+ * * `roughness` widens the specular reflections and transmission, from a mirror (0) to very rough
+ *   (1).
+ * * `specular` is the strength of the reflections from a dielectric, 0.5 being an index of
+ *   refraction of 1.5; it also sets the index of refraction for transmission.
+ * * `sheen` adds a white glow towards grazing angles, as from cloth.
+ * * `clearcoat` adds a glossy, colourless coat, as on car paint.
+ * * `transmission` blends the diffuse surface into glass.
+ * * `subsurface` flattens the diffuse surface, as if light were scattered beneath it.
+ *
+ * All are in [0, 1], and when they come from textures, their luminance is used.  At the extremes
+ * the material behaves like the simpler ones: with the defaults it is a Lambertian surface with a
+ * faint specular reflection, with `metallic` 1 it is a rough metal like `Conductor`, and with
+ * `transmission` 1 and `roughness` 0 it is glass like `Dielectric`.  Objects with transmission
+ * must be closed, as their interior is treated as glass.
+ */
+#[derive(Debug)]
+pub struct Principled {
+    base_colour: Arc<dyn Texture>,
+    metallic: Arc<dyn Texture>,
+    roughness: Arc<dyn Texture>,
+    specular: Arc<dyn Texture>,
+    sheen: Arc<dyn Texture>,
+    clearcoat: Arc<dyn Texture>,
+    transmission: Arc<dyn Texture>,
+    subsurface: Arc<dyn Texture>,
+}
+
+impl Principled {
+    /**
+     * Creates a principled material with `base_colour`, uniform `metallic` and `roughness`, and
+     * the default specular strength of 0.5 and nothing else.
+     */
+    #[must_use]
+    pub fn new(base_colour: impl Into<Arc<dyn Texture>>, metallic: f64, roughness: f64) -> Self {
+        Self::new_textured(base_colour, grey(metallic), grey(roughness))
+    }
+
+    #[must_use]
+    pub fn new_material(
+        base_colour: impl Into<Arc<dyn Texture>>,
+        metallic: f64,
+        roughness: f64,
+    ) -> Arc<dyn Material> {
+        Arc::new(Self::new(base_colour, metallic, roughness))
+    }
+
+    /**
+     * Creates a principled material with `base_colour`, and `metallic` and `roughness` varying
+     * over the surface.
+     */
+    #[must_use]
+    pub fn new_textured(
+        base_colour: impl Into<Arc<dyn Texture>>,
+        metallic: impl Into<Arc<dyn Texture>>,
+        roughness: impl Into<Arc<dyn Texture>>,
+    ) -> Self {
+        Self {
+            base_colour: base_colour.into(),
+            metallic: metallic.into(),
+            roughness: roughness.into(),
+            specular: grey(0.5),
+            sheen: grey(0.0),
+            clearcoat: grey(0.0),
+            transmission: grey(0.0),
+            subsurface: grey(0.0),
+        }
+    }
+
+    #[must_use]
+    pub fn new_material_textured(
+        base_colour: impl Into<Arc<dyn Texture>>,
+        metallic: impl Into<Arc<dyn Texture>>,
+        roughness: impl Into<Arc<dyn Texture>>,
+    ) -> Arc<dyn Material> {
+        Arc::new(Self::new_textured(base_colour, metallic, roughness))
+    }
+
+    pub fn set_specular(&mut self, specular: f64) {
+        self.specular = grey(specular);
+    }
+
+    pub fn set_specular_textured(&mut self, specular: impl Into<Arc<dyn Texture>>) {
+        self.specular = specular.into();
+    }
+
+    pub fn set_sheen(&mut self, sheen: f64) {
+        self.sheen = grey(sheen);
+    }
+
+    pub fn set_sheen_textured(&mut self, sheen: impl Into<Arc<dyn Texture>>) {
+        self.sheen = sheen.into();
+    }
+
+    pub fn set_clearcoat(&mut self, clearcoat: f64) {
+        self.clearcoat = grey(clearcoat);
+    }
+
+    pub fn set_clearcoat_textured(&mut self, clearcoat: impl Into<Arc<dyn Texture>>) {
+        self.clearcoat = clearcoat.into();
+    }
+
+    pub fn set_transmission(&mut self, transmission: f64) {
+        self.transmission = grey(transmission);
+    }
+
+    pub fn set_transmission_textured(&mut self, transmission: impl Into<Arc<dyn Texture>>) {
+        self.transmission = transmission.into();
+    }
+
+    pub fn set_subsurface(&mut self, subsurface: f64) {
+        self.subsurface = grey(subsurface);
+    }
+
+    pub fn set_subsurface_textured(&mut self, subsurface: impl Into<Arc<dyn Texture>>) {
+        self.subsurface = subsurface.into();
+    }
+
+    /**
+     * Returns the lobes of the material at `rec`.
+     */
+    #[must_use]
+    fn lobes(&self, rec: &HitRecord) -> Lobes {
+        let (u, v, p) = (rec.u(), rec.v(), rec.p());
+        let scalar =
+            |texture: &Arc<dyn Texture>| texture.value(u, v, p).luminance().clamp(0.0, 1.0);
+
+        let roughness = scalar(&self.roughness);
+        let f0 = (0.08 * scalar(&self.specular)).max(1e-4);
+        let ir = (1.0 + f0.sqrt()) / (1.0 - f0.sqrt());
+        let base_colour = self.base_colour.value(u, v, p);
+        let transmission = scalar(&self.transmission);
+        let specular = Ggx::new(roughness.max(MIN_ROUGHNESS), roughness.max(MIN_ROUGHNESS));
+        let frame = Frame::new(rec.normal(), rec.tangent());
+
+        // Seen from behind, the opaque part of the surface is the same as from the front, but the
+        // transmissive part is the inside of glass, whose colour was taken on the way in.
+        let (eta, transmission_colour) = if rec.front_face() {
+            (ir, base_colour)
+        } else {
+            (ir.recip(), Colour(1.0, 1.0, 1.0))
+        };
+
+        Lobes {
+            frame,
+            base_colour,
+            transmission_colour,
+            metallic: scalar(&self.metallic),
+            roughness,
+            sheen: scalar(&self.sheen),
+            clearcoat: scalar(&self.clearcoat),
+            transmission,
+            subsurface: scalar(&self.subsurface),
+            specular,
+            coat: Ggx::new(CLEARCOAT_ROUGHNESS, CLEARCOAT_ROUGHNESS),
+            ir,
+            eta,
+        }
+    }
+}
+
+/**
+ * Returns a uniform grey texture of luminance `value`.
+ */
+fn grey(value: f64) -> Arc<dyn Texture> {
+    SolidColour::new_texture(Colour(value, value, value))
+}
+
+/**
+ * Type for the parameters of a principled material at a hit, in the local frame of the surface.
+ */
+#[derive(Clone, Copy, Debug)]
+struct Lobes {
+    frame: Frame,
+    base_colour: Colour,
+    transmission_colour: Colour,
+    metallic: f64,
+    roughness: f64,
+    sheen: f64,
+    clearcoat: f64,
+    transmission: f64,
+    subsurface: f64,
+    specular: Ggx,
+    coat: Ggx,
+    ir: f64,  // Index of refraction of the opaque part of the surface.
+    eta: f64, // Ratio of the index of refraction behind the glass part to that in front.
+}
+
+impl Lobes {
+    /**
+     * Returns the Fresnel reflectance of the dielectric part of the surface for light at an angle
+     * with cosine `cos_theta` to a microfacet, blending the opaque part with the glass part.
+     */
+    #[must_use]
+    fn fresnel(&self, cos_theta: f64) -> f64 {
+        (1.0 - self.transmission) * fresnel_dielectric(cos_theta, self.ir)
+            + self.transmission * fresnel_dielectric(cos_theta, self.eta)
+    }
+
+    /**
+     * Returns the probabilities of sampling the diffuse, specular, transmission and clear coat
+     * lobes for `wo`, roughly in proportion to the light they scatter, or `None` if nothing is
+     * scattered.
+     */
+    #[must_use]
+    fn probabilities(&self, wo: Vec3) -> Option<[f64; 4]> {
+        let dielectric = 1.0 - self.metallic;
+        let transmittance = 1.0 - fresnel_dielectric(wo.z(), self.eta);
+        let weights = [
+            dielectric * (1.0 - self.transmission),
+            self.metallic + dielectric * self.fresnel(wo.z()),
+            dielectric * self.transmission * transmittance.max(0.1),
+            0.25 * self.clearcoat * schlick(0.04, wo.z()),
+        ];
+
+        let total: f64 = weights.iter().sum();
+        if total <= 0.0 {
+            return None;
+        }
+        Some(weights.map(|w| w / total))
+    }
+
+    /**
+     * Returns the fraction of light arriving from `wi` that leaves towards `wo` (the BSDF times
+     * the cosine), and the probability density of sampling `wi` from all the lobes.
+     */
+    #[must_use]
+    fn evaluate(&self, wo: Vec3, wi: Vec3, probabilities: [f64; 4]) -> (Colour, f64) {
+        let black = Colour(0.0, 0.0, 0.0);
+        let dielectric = 1.0 - self.metallic;
+        let mut value = black;
+        let mut pdf = 0.0;
+
+        if wi.z() > 0.0 {
+            let h = (wo + wi).unit();
+            let cos_d = wi.dot(h).max(0.0);
+
+            // Diffuse, flattened towards subsurface scattering, with sheen.
+            let diffuse_weight = dielectric * (1.0 - self.transmission);
+            if diffuse_weight > 0.0 {
+                let fl = (1.0 - wi.z()).powi(5);
+                let fv = (1.0 - wo.z()).powi(5);
+                let fss90 = self.roughness * cos_d * cos_d;
+                let fss = (1.0 + (fss90 - 1.0) * fl) * (1.0 + (fss90 - 1.0) * fv);
+                let ss = 1.25 * (fss * ((wi.z() + wo.z()).recip() - 0.5) + 0.5);
+                let flatten = 1.0 + self.subsurface * (ss - 1.0);
+                let sheen = self.sheen * (1.0 - cos_d).powi(5);
+                value += (diffuse_weight * wi.z())
+                    * (consts::FRAC_1_PI * flatten * self.base_colour
+                        + Colour(sheen, sheen, sheen));
+            }
+            pdf += probabilities[0] * wi.z() * consts::FRAC_1_PI;
+
+            // Specular reflection, from a dielectric or a metal.
+            let cos_h = wo.dot(h);
+            if cos_h > 0.0 {
+                let f_dielectric = self.fresnel(cos_h);
+                let f_metal = Colour(
+                    schlick(self.base_colour.0, cos_h),
+                    schlick(self.base_colour.1, cos_h),
+                    schlick(self.base_colour.2, cos_h),
+                );
+                let f = dielectric * Colour(f_dielectric, f_dielectric, f_dielectric)
+                    + self.metallic * f_metal;
+                let ggx = self.specular;
+                value += (ggx.d(h) * ggx.g(wo, wi) / (4.0 * wo.z())) * f;
+
+                // The transmission lobe also reflects at microfacets with total internal
+                // reflection.
+                let probability = if refract(wo, h, self.eta).is_some() {
+                    probabilities[1]
+                } else {
+                    probabilities[1] + probabilities[2]
+                };
+                pdf += probability * ggx.visible_normal_pdf(wo, h) / (4.0 * cos_h);
+
+                // Clear coat.
+                if self.clearcoat > 0.0 {
+                    let coat = self.coat;
+                    let f = 0.25 * self.clearcoat * schlick(0.04, cos_h);
+                    let v = f * coat.d(h) * coat.g(wo, wi) / (4.0 * wo.z());
+                    value += Colour(v, v, v);
+                    pdf += probabilities[3] * coat.visible_normal_pdf(wo, h) / (4.0 * cos_h);
+                }
+            }
+        } else if wi.z() < 0.0 {
+            // Transmission, with the half vector flipped to face the front of the surface.
+            let h = (wo + self.eta * wi).unit();
+            let h = if h.z() < 0.0 { -h } else { h };
+            let (cos_o, cos_i) = (wo.dot(h), wi.dot(h));
+            if cos_o <= 0.0 || cos_i >= 0.0 {
+                return (black, 0.0);
+            }
+
+            let ggx = self.specular;
+            let transmittance = 1.0 - fresnel_dielectric(cos_o, self.eta);
+            let jacobian = -cos_i / (cos_i + cos_o / self.eta).powi(2);
+            let v = dielectric
+                * self.transmission
+                * ggx.d(h)
+                * ggx.g(wo, wi)
+                * transmittance
+                * cos_o
+                * jacobian
+                / (wo.z() * self.eta * self.eta);
+            value += v * self.transmission_colour;
+            pdf += probabilities[2] * ggx.visible_normal_pdf(wo, h) * jacobian;
+        }
+
+        (value, pdf)
+    }
+}
+
+/**
+ * Returns Schlick’s approximation to the Fresnel reflectance, for reflectance `f0` at normal
+ * incidence.
+ */
+fn schlick(f0: f64, cos_theta: f64) -> f64 {
+    f0 + (1.0 - f0) * (1.0 - cos_theta.clamp(0.0, 1.0)).powi(5)
+}
+
+impl Material for Principled {
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        sampler: &mut dyn Sampler,
+    ) -> Option<(Colour, Ray)> {
+        let lobes = self.lobes(rec);
+        let wo = lobes.frame.to_local(-r_in.direction().unit());
+        if wo.z() <= 0.0 {
+            return None;
+        }
+        let probabilities = lobes.probabilities(wo)?;
+
+        // Sample one lobe, but weight the direction by the density of sampling it from any lobe.
+        let choice = sampler.get_1d();
+        let sample = sampler.get_2d();
+        // Directions on the wrong side of the surface for their lobe are lost, as the density of
+        // sampling them is not counted.
+        let reflect = |h: Vec3| {
+            let wi = (-wo).reflect(h);
+            if wi.z() > 0.0 {
+                Some(wi)
+            } else {
+                None
+            }
+        };
+        let wi = if choice < probabilities[0] {
+            let direction = Vec3(0.0, 0.0, 1.0) + Vec3::new_unit_from_sample(sample);
+            if direction.is_near_zero() {
+                Vec3(0.0, 0.0, 1.0)
+            } else {
+                direction.unit()
+            }
+        } else if choice < probabilities[0] + probabilities[1] {
+            reflect(lobes.specular.sample_visible_normal(wo, sample))?
+        } else if choice < probabilities[0] + probabilities[1] + probabilities[2] {
+            // Microfacets that totally internally reflect the light reflect it instead.
+            let h = lobes.specular.sample_visible_normal(wo, sample);
+            match refract(wo, h, lobes.eta) {
+                Some(wi) if wi.z() < 0.0 => wi,
+                Some(_) => return None,
+                None => reflect(h)?,
+            }
+        } else {
+            reflect(lobes.coat.sample_visible_normal(wo, sample))?
+        };
+
+        let (value, pdf) = lobes.evaluate(wo, wi, probabilities);
+        if pdf <= 0.0 {
+            return None;
+        }
+        let scattered = Ray::new(rec.p(), lobes.frame.to_world(wi), r_in.time());
+        Some((value / pdf, scattered))
+    }
+
+    fn eval(&self, r_in: &Ray, rec: &HitRecord, direction: Vec3) -> Option<(Colour, f64)> {
+        let lobes = self.lobes(rec);
+        let wo = lobes.frame.to_local(-r_in.direction().unit());
+        let wi = lobes.frame.to_local(direction.unit());
+        if wo.z() <= 0.0 {
+            return None;
+        }
+        let probabilities = lobes.probabilities(wo)?;
+
+        let (value, pdf) = lobes.evaluate(wo, wi, probabilities);
+        if pdf <= 0.0 {
+            return None;
+        }
+        Some((value, pdf))
+    }
+}
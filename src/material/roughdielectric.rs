@@ -0,0 +1,170 @@
+use super::{
+    microfacet::{fresnel_dielectric, refract, Frame, Ggx},
+    Material,
+};
+use crate::{Colour, HitRecord, Ray, Sampler, SolidColour, Texture, Vec3};
+use std::sync::Arc;
+
+/**
+ * Type for representing a transparent material with a rough surface, such as frosted glass, as
+ * microscopic facets with normals in a GGX distribution (after Walter et al.).
+ *
+ * Light is reflected or refracted at each facet in proportion to the exact Fresnel equations for
+ * unpolarised light.  The roughness is in [0, 1]: 0 is perfectly smooth glass and 1 is very
+ * rough.  When it comes from a texture, the luminance of the texture is used.
+ */
+#[derive(Debug)]
+pub struct RoughDielectric {
+    ir: f64, // Index of refraction.
+    roughness: Arc<dyn Texture>,
+}
+
+impl RoughDielectric {
+    /**
+     * Creates a rough dielectric with index of refraction `ir` and a uniform `roughness`.
+     */
+    #[must_use]
+    pub fn new(ir: f64, roughness: f64) -> Self {
+        Self::new_textured(
+            ir,
+            SolidColour::new_texture(Colour(roughness, roughness, roughness)),
+        )
+    }
+
+    #[must_use]
+    pub fn new_material(ir: f64, roughness: f64) -> Arc<dyn Material> {
+        Arc::new(Self::new(ir, roughness))
+    }
+
+    /**
+     * Creates a rough dielectric with index of refraction `ir` and a roughness varying over the
+     * surface.
+     */
+    #[must_use]
+    pub fn new_textured(ir: f64, roughness: impl Into<Arc<dyn Texture>>) -> Self {
+        assert!(ir > 0.0);
+        Self {
+            ir,
+            roughness: roughness.into(),
+        }
+    }
+
+    #[must_use]
+    pub fn new_material_textured(
+        ir: f64,
+        roughness: impl Into<Arc<dyn Texture>>,
+    ) -> Arc<dyn Material> {
+        Arc::new(Self::new_textured(ir, roughness))
+    }
+
+    /**
+     * Returns the microfacet distribution and the local frame at `rec`, and the ratio of the
+     * index of refraction behind the surface to that in front.
+     */
+    #[must_use]
+    fn distribution(&self, rec: &HitRecord) -> (Ggx, Frame, f64) {
+        let roughness = self.roughness.value(rec.u(), rec.v(), rec.p()).luminance();
+        let eta = if rec.front_face() {
+            self.ir
+        } else {
+            self.ir.recip()
+        };
+        (
+            Ggx::new(roughness, roughness),
+            Frame::new(rec.normal(), rec.tangent()),
+            eta,
+        )
+    }
+}
+
+impl Material for RoughDielectric {
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        sampler: &mut dyn Sampler,
+    ) -> Option<(Colour, Ray)> {
+        let (ggx, frame, eta) = self.distribution(rec);
+        let wo = frame.to_local(-r_in.direction().unit());
+        if wo.z() <= 0.0 {
+            return None;
+        }
+
+        let sample = sampler.get_2d();
+        let choice = sampler.get_1d();
+        let h = if ggx.is_smooth() {
+            Vec3(0.0, 0.0, 1.0)
+        } else {
+            ggx.sample_visible_normal(wo, sample)
+        };
+
+        // Choose between reflection and refraction in proportion to the Fresnel reflectance, so
+        // that it cancels out of the weight.
+        let reflectance = fresnel_dielectric(wo.dot(h), eta);
+        let (wi, scale) = if choice < reflectance {
+            let wi = (-wo).reflect(h);
+            if wi.z() <= 0.0 {
+                return None;
+            }
+            (wi, 1.0)
+        } else {
+            let wi = refract(wo, h, eta)?;
+            if wi.z() >= 0.0 {
+                return None;
+            }
+            // Radiance is concentrated or spread out by the change in solid angle.
+            (wi, (eta * eta).recip())
+        };
+
+        // The distribution and most of the masking cancel with the sampling density.
+        let attenuation = if ggx.is_smooth() {
+            scale
+        } else {
+            scale * ggx.g(wo, wi) / ggx.g1(wo)
+        };
+        let scattered = Ray::new(rec.p(), frame.to_world(wi), r_in.time());
+        Some((Colour(attenuation, attenuation, attenuation), scattered))
+    }
+
+    fn eval(&self, r_in: &Ray, rec: &HitRecord, direction: Vec3) -> Option<(Colour, f64)> {
+        let (ggx, frame, eta) = self.distribution(rec);
+        if ggx.is_smooth() {
+            return None;
+        }
+
+        let wo = frame.to_local(-r_in.direction().unit());
+        let wi = frame.to_local(direction.unit());
+        if wo.z() <= 0.0 || wi.z() == 0.0 {
+            return None;
+        }
+
+        let (value, pdf) = if wi.z() > 0.0 {
+            let h = (wo + wi).unit();
+            let reflectance = fresnel_dielectric(wo.dot(h), eta);
+            let pdf = ggx.visible_normal_pdf(wo, h) * reflectance / (4.0 * wo.dot(h));
+            let value = ggx.d(h) * ggx.g(wo, wi) * reflectance / (4.0 * wo.z());
+            (value, pdf)
+        } else {
+            // The half vector of a refraction, flipped to face the front of the surface.
+            let h = (wo + eta * wi).unit();
+            let h = if h.z() < 0.0 { -h } else { h };
+            let (cos_o, cos_i) = (wo.dot(h), wi.dot(h));
+            if cos_o <= 0.0 || cos_i >= 0.0 {
+                return None;
+            }
+
+            let transmittance = 1.0 - fresnel_dielectric(cos_o, eta);
+            let denominator = (cos_i + cos_o / eta).powi(2);
+            let jacobian = -cos_i / denominator;
+            let pdf = ggx.visible_normal_pdf(wo, h) * jacobian * transmittance;
+            let value =
+                ggx.d(h) * ggx.g(wo, wi) * transmittance * cos_o * jacobian / (wo.z() * eta * eta);
+            (value, pdf)
+        };
+
+        if pdf <= 0.0 {
+            return None;
+        }
+        Some((Colour(value, value, value), pdf))
+    }
+}
@@ -0,0 +1,109 @@
+use crate::Vec3;
+use std::ops;
+
+/**
+ * Type for representing rotations as unit quaternions.
+ */
+#[derive(Clone, Copy, Debug)]
+pub struct Quaternion(pub f64, pub Vec3);
+
+impl Quaternion {
+    /**
+     * Creates the quaternion representing no rotation.
+     */
+    #[must_use]
+    pub const fn new_identity() -> Self {
+        Self(1.0, Vec3(0.0, 0.0, 0.0))
+    }
+
+    /**
+     * Creates a quaternion representing a rotation by `theta` degrees about `axis`.
+     */
+    #[must_use]
+    pub fn new_rotation(axis: Vec3, theta: f64) -> Self {
+        let (sin_half, cos_half) = (0.5 * theta.to_radians()).sin_cos();
+        Self(cos_half, sin_half * axis.unit())
+    }
+
+    #[must_use]
+    pub const fn w(self) -> f64 {
+        self.0
+    }
+
+    #[must_use]
+    pub const fn xyz(self) -> Vec3 {
+        self.1
+    }
+
+    #[must_use]
+    pub fn dot(self, q: Self) -> f64 {
+        self.0 * q.0 + self.1.dot(q.1)
+    }
+
+    #[must_use]
+    pub fn conjugate(self) -> Self {
+        Self(self.0, -self.1)
+    }
+
+    #[must_use]
+    pub fn unit(self) -> Self {
+        let length = self.dot(self).sqrt();
+        Self(self.0 / length, self.1 / length)
+    }
+
+    /**
+     * Rotates the vector `v`.
+     */
+    #[must_use]
+    pub fn rotate(self, v: Vec3) -> Vec3 {
+        let Self(w, u) = self;
+        let t = 2.0 * u.cross(v);
+        v + w * t + u.cross(t)
+    }
+
+    /**
+     * Spherical linear interpolation from `self` (at `t` = 0) to `q` (at `t` = 1), taking the
+     * shorter path.
+     */
+    #[must_use]
+    pub fn slerp(self, q: Self, t: f64) -> Self {
+        let mut cos_theta = self.dot(q);
+        let q = if cos_theta < 0.0 {
+            cos_theta = -cos_theta;
+            Self(-q.0, -q.1)
+        } else {
+            q
+        };
+
+        // Fall back to linear interpolation when the rotations are almost identical.
+        let (a, b) = if cos_theta > 0.9995 {
+            (1.0 - t, t)
+        } else {
+            let theta = cos_theta.acos();
+            let sin_theta = theta.sin();
+            (
+                ((1.0 - t) * theta).sin() / sin_theta,
+                (t * theta).sin() / sin_theta,
+            )
+        };
+
+        Self(a * self.0 + b * q.0, a * self.1 + b * q.1).unit()
+    }
+}
+
+impl Default for Quaternion {
+    fn default() -> Self {
+        Self::new_identity()
+    }
+}
+
+impl ops::Mul for Quaternion {
+    type Output = Self;
+    fn mul(self, q: Self) -> Self::Output {
+        let Quaternion(w1, v1) = self;
+        let Quaternion(w2, v2) = q;
+        let w = w1 * w2 - v1.dot(v2);
+        let v = w1 * v2 + w2 * v1 + v1.cross(v2);
+        Self(w, v)
+    }
+}
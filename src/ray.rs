@@ -1,13 +1,18 @@
-use crate::Vec3;
+use crate::{spectrum::SAMPLES, Vec3};
 
 /**
  * Type to represent a ray of light.
+ *
+ * When rendering spectrally, a ray carries a few wavelengths of light, the first of which is the
+ * hero wavelength that decides the path of dispersed light; otherwise it carries all colours at
+ * once.
  */
 #[derive(Clone, Debug)]
 pub struct Ray {
     origin: Vec3,
     direction: Vec3,
     time: f64,
+    wavelengths: Option<[f64; SAMPLES]>,
 }
 
 impl Ray {
@@ -17,6 +22,18 @@ impl Ray {
             origin,
             direction,
             time,
+            wavelengths: None,
+        }
+    }
+
+    /**
+     * Returns the ray carrying light of `wavelengths`, in nanometres, or all colours if `None`.
+     */
+    #[must_use]
+    pub const fn with_wavelengths(self, wavelengths: Option<[f64; SAMPLES]>) -> Self {
+        Self {
+            wavelengths,
+            ..self
         }
     }
 
@@ -35,6 +52,19 @@ impl Ray {
         self.time
     }
 
+    #[must_use]
+    pub const fn wavelengths(&self) -> Option<[f64; SAMPLES]> {
+        self.wavelengths
+    }
+
+    /**
+     * Returns the hero wavelength, if the ray carries particular wavelengths.
+     */
+    #[must_use]
+    pub fn wavelength(&self) -> Option<f64> {
+        self.wavelengths.map(|wavelengths| wavelengths[0])
+    }
+
     #[must_use]
     pub fn at(&self, t: f64) -> Vec3 {
         self.origin + t * self.direction
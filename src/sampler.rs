@@ -0,0 +1,147 @@
+mod bluenoise;
+mod halton;
+mod independent;
+mod sobol;
+mod stratified;
+pub use bluenoise::BlueNoiseSampler;
+pub use halton::HaltonSampler;
+pub use independent::IndependentSampler;
+pub use sobol::SobolSampler;
+use std::fmt::Debug;
+pub use stratified::StratifiedSampler;
+
+/**
+ * Trait for generators of sample values in [0, 1).
+ *
+ * Each sample of a pixel is a point in a high-dimensional space: the first dimensions select the
+ * position within the pixel, the next ones the position on the lens and the time, and later ones
+ * the choices made at each bounce of the path.  Calls to `get_1d` and `get_2d` consume dimensions
+ * in order.  Well-distributed samplers spread the values of each dimension evenly over the
+ * samples of a pixel.
+ */
+pub trait Sampler: Debug + Send {
+    /**
+     * Starts generating the dimensions of sample `index` (out of `samples_per_pixel`) of the
+     * pixel at `pixel`.
+     */
+    fn start_sample(&mut self, pixel: (u32, u32), index: u32, samples_per_pixel: u32);
+
+    #[must_use]
+    fn get_1d(&mut self) -> f64;
+
+    #[must_use]
+    fn get_2d(&mut self) -> (f64, f64);
+
+    /**
+     * Creates an independent copy of the sampler, for use by another thread.
+     */
+    #[must_use]
+    fn clone_sampler(&self) -> Box<dyn Sampler>;
+}
+
+/**
+ * Type for keeping track of the current sample of a sampler.
+ */
+#[derive(Clone, Debug, Default)]
+struct SampleState {
+    pixel: (u32, u32),
+    index: u32,
+    samples_per_pixel: u32,
+    dimension: u32,
+}
+
+impl SampleState {
+    fn start(&mut self, pixel: (u32, u32), index: u32, samples_per_pixel: u32) {
+        assert!(index < samples_per_pixel);
+        self.pixel = pixel;
+        self.index = index;
+        self.samples_per_pixel = samples_per_pixel;
+        self.dimension = 0;
+    }
+
+    /**
+     * Returns a hash of the current pixel and dimension, and advances to the next dimension.
+     */
+    #[must_use]
+    fn next_dimension(&mut self, seed: u64) -> u64 {
+        let (x, y) = self.pixel;
+        let hash = mix_bits(
+            mix_bits(mix_bits(seed) ^ (u64::from(x) << 32 | u64::from(y)))
+                ^ u64::from(self.dimension),
+        );
+        self.dimension += 1;
+        hash
+    }
+}
+
+/**
+ * Scrambles the bits of a 64-bit value.
+ */
+#[must_use]
+pub(crate) fn mix_bits(mut v: u64) -> u64 {
+    v ^= v >> 31;
+    v = v.wrapping_mul(0x7fb5_d329_728e_a185);
+    v ^= v >> 27;
+    v = v.wrapping_mul(0x81da_def4_bc2d_d44d);
+    v ^= v >> 33;
+    v
+}
+
+/**
+ * Converts the top 53 bits of a 64-bit value to a number in [0, 1).
+ */
+#[must_use]
+pub(crate) fn to_unit_f64(v: u64) -> f64 {
+    (v >> 11) as f64 * (1.0 / (1_u64 << 53) as f64)
+}
+
+/**
+ * Converts a 32-bit fixed-point fraction to a number in [0, 1).
+ */
+#[must_use]
+fn fraction_to_f64(v: u32) -> f64 {
+    f64::from(v) * (1.0 / (1_u64 << 32) as f64)
+}
+
+/**
+ * Returns element `i` of a pseudo-random permutation of `0..l`, selected by `p`.  (Kensler,
+ * “Correlated Multi-Jittered Sampling”, 2013.)
+ */
+#[must_use]
+fn permutation_element(mut i: u32, l: u32, p: u32) -> u32 {
+    assert!(i < l);
+
+    let mut w = l - 1;
+    w |= w >> 1;
+    w |= w >> 2;
+    w |= w >> 4;
+    w |= w >> 8;
+    w |= w >> 16;
+
+    // Cycle-walk until the hashed index falls within range.
+    loop {
+        i ^= p;
+        i = i.wrapping_mul(0xe170_893d);
+        i ^= p >> 16;
+        i ^= (i & w) >> 4;
+        i ^= p >> 8;
+        i = i.wrapping_mul(0x0929_eb3f);
+        i ^= p >> 23;
+        i ^= (i & w) >> 1;
+        i = i.wrapping_mul(1 | p >> 27);
+        i = i.wrapping_mul(0x6935_fa69);
+        i ^= (i & w) >> 11;
+        i = i.wrapping_mul(0x74dc_b303);
+        i ^= (i & w) >> 2;
+        i = i.wrapping_mul(0x9e50_1cc3);
+        i ^= (i & w) >> 2;
+        i = i.wrapping_mul(0xc860_a3df);
+        i &= w;
+        i ^= i >> 5;
+        if i < l {
+            break;
+        }
+    }
+
+    (i + p % l) % l
+}
@@ -0,0 +1,193 @@
+use super::{mix_bits, SampleState, Sampler};
+use std::sync::OnceLock;
+
+/**
+ * Size of the tileable blue-noise mask, in pixels.
+ */
+const MASK_SIZE: usize = 64;
+
+/**
+ * Type for a blue-noise dithered sampler.
+ *
+ * The samples of each pixel follow a low-discrepancy (Kronecker) sequence, randomly shifted by a
+ * blue-noise mask (Georgiev and Fajardo, “Blue-noise Dithered Sampling”, 2016).  Neighbouring
+ * pixels therefore receive very different shifts, which spreads the remaining error as
+ * high-frequency noise that is less visible and easier to filter.  Each dimension uses a different
+ * toroidal offset into the mask.
+ */
+#[derive(Clone, Debug, Default)]
+pub struct BlueNoiseSampler {
+    seed: u64,
+    state: SampleState,
+}
+
+impl BlueNoiseSampler {
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            state: SampleState::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn new_sampler(seed: u64) -> Box<dyn Sampler> {
+        Box::new(Self::new(seed))
+    }
+
+    /**
+     * Looks up the blue-noise mask for the current pixel, with an offset selected by `hash`.
+     */
+    #[must_use]
+    fn mask_value(&self, hash: u64) -> f64 {
+        let (x, y) = self.state.pixel;
+        let x = (x as usize + (hash as usize % MASK_SIZE)) % MASK_SIZE;
+        let y = (y as usize + ((hash >> 16) as usize % MASK_SIZE)) % MASK_SIZE;
+        mask()[y * MASK_SIZE + x]
+    }
+}
+
+impl Sampler for BlueNoiseSampler {
+    fn start_sample(&mut self, pixel: (u32, u32), index: u32, samples_per_pixel: u32) {
+        self.state.start(pixel, index, samples_per_pixel);
+    }
+
+    fn get_1d(&mut self) -> f64 {
+        // The golden ratio sequence.
+        const ALPHA: f64 = 0.618_033_988_749_894_9;
+
+        let hash = self.state.next_dimension(self.seed);
+        let shift = self.mask_value(hash);
+        (shift + f64::from(self.state.index) * ALPHA).fract()
+    }
+
+    fn get_2d(&mut self) -> (f64, f64) {
+        // The R2 sequence, based on the plastic number.
+        const G: f64 = 1.324_717_957_244_746;
+        const ALPHA_X: f64 = 1.0 / G;
+        const ALPHA_Y: f64 = 1.0 / (G * G);
+
+        let hash = self.state.next_dimension(self.seed);
+        let shift_x = self.mask_value(hash);
+        let shift_y = self.mask_value(hash >> 32);
+        let index = f64::from(self.state.index);
+        (
+            (shift_x + index * ALPHA_X).fract(),
+            (shift_y + index * ALPHA_Y).fract(),
+        )
+    }
+
+    fn clone_sampler(&self) -> Box<dyn Sampler> {
+        Box::new(self.clone())
+    }
+}
+
+/**
+ * Returns the blue-noise mask, generating it on first use.
+ */
+#[must_use]
+fn mask() -> &'static [f64] {
+    static MASK: OnceLock<Box<[f64]>> = OnceLock::new();
+    MASK.get_or_init(generate_mask)
+}
+
+/**
+ * Generates a tileable blue-noise mask by the void-and-cluster method (Ulichney, 1993).  Each
+ * pixel gets a distinct rank, scaled to [0, 1).
+ */
+#[must_use]
+fn generate_mask() -> Box<[f64]> {
+    const N: usize = MASK_SIZE * MASK_SIZE;
+    const SIGMA: f64 = 1.5;
+
+    // Gaussian energy of a point at each toroidal offset.
+    let mut kernel = vec![0.0; N];
+    for dy in 0..MASK_SIZE {
+        for dx in 0..MASK_SIZE {
+            let x = dx.min(MASK_SIZE - dx) as f64;
+            let y = dy.min(MASK_SIZE - dy) as f64;
+            kernel[dy * MASK_SIZE + dx] = (-(x * x + y * y) / (2.0 * SIGMA * SIGMA)).exp();
+        }
+    }
+
+    let update = |energy: &mut [f64], p: usize, sign: f64| {
+        let (px, py) = (p % MASK_SIZE, p / MASK_SIZE);
+        for y in 0..MASK_SIZE {
+            let dy = (y + MASK_SIZE - py) % MASK_SIZE;
+            for x in 0..MASK_SIZE {
+                let dx = (x + MASK_SIZE - px) % MASK_SIZE;
+                energy[y * MASK_SIZE + x] += sign * kernel[dy * MASK_SIZE + dx];
+            }
+        }
+    };
+
+    // The densest point, or the emptiest space.
+    let tightest_cluster = |pattern: &[bool], energy: &[f64]| {
+        (0..N)
+            .filter(|&p| pattern[p])
+            .max_by(|&a, &b| energy[a].partial_cmp(&energy[b]).unwrap())
+            .unwrap()
+    };
+    let largest_void = |pattern: &[bool], energy: &[f64]| {
+        (0..N)
+            .filter(|&p| !pattern[p])
+            .min_by(|&a, &b| energy[a].partial_cmp(&energy[b]).unwrap())
+            .unwrap()
+    };
+
+    // Start with a random pattern of about one point in ten.
+    let mut pattern = vec![false; N];
+    let mut energy = vec![0.0; N];
+    let mut rand_state = 0x5eed_u64;
+    let mut ones = 0;
+    while ones < N / 10 {
+        rand_state = mix_bits(rand_state.wrapping_add(1));
+        let p = (rand_state % N as u64) as usize;
+        if !pattern[p] {
+            pattern[p] = true;
+            update(&mut energy, p, 1.0);
+            ones += 1;
+        }
+    }
+
+    // Even it out, by moving points from the tightest cluster into the largest void.
+    loop {
+        let cluster = tightest_cluster(&pattern, &energy);
+        pattern[cluster] = false;
+        update(&mut energy, cluster, -1.0);
+
+        let void = largest_void(&pattern, &energy);
+        pattern[void] = true;
+        update(&mut energy, void, 1.0);
+
+        if void == cluster {
+            break;
+        }
+    }
+
+    let mut rank = vec![0; N];
+
+    // Rank the initial points, removing them from the densest clusters first.
+    {
+        let mut pattern = pattern.clone();
+        let mut energy = energy.clone();
+        for r in (0..ones).rev() {
+            let cluster = tightest_cluster(&pattern, &energy);
+            pattern[cluster] = false;
+            update(&mut energy, cluster, -1.0);
+            rank[cluster] = r;
+        }
+    }
+
+    // Rank the remaining points, filling the largest voids first.
+    for r in ones..N {
+        let void = largest_void(&pattern, &energy);
+        pattern[void] = true;
+        update(&mut energy, void, 1.0);
+        rank[void] = r;
+    }
+
+    rank.into_iter()
+        .map(|r| (r as f64 + 0.5) / N as f64)
+        .collect()
+}
@@ -0,0 +1,86 @@
+use super::{mix_bits, to_unit_f64, SampleState, Sampler};
+
+/**
+ * Bases for the dimensions of the Halton sequence.
+ */
+const PRIMES: [u32; 64] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+    101, 103, 107, 109, 113, 127, 131, 137, 139, 149, 151, 157, 163, 167, 173, 179, 181, 191, 193,
+    197, 199, 211, 223, 227, 229, 233, 239, 241, 251, 257, 263, 269, 271, 277, 281, 283, 293, 307,
+    311,
+];
+
+/**
+ * Type for a sampler based on the Halton sequence.
+ *
+ * Each pixel uses the same points, randomly shifted per pixel and dimension (Cranley–Patterson
+ * rotation).  Dimensions beyond the table of prime bases fall back to independent random values.
+ */
+#[derive(Clone, Debug, Default)]
+pub struct HaltonSampler {
+    seed: u64,
+    state: SampleState,
+}
+
+impl HaltonSampler {
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            state: SampleState::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn new_sampler(seed: u64) -> Box<dyn Sampler> {
+        Box::new(Self::new(seed))
+    }
+}
+
+impl Sampler for HaltonSampler {
+    fn start_sample(&mut self, pixel: (u32, u32), index: u32, samples_per_pixel: u32) {
+        self.state.start(pixel, index, samples_per_pixel);
+    }
+
+    fn get_1d(&mut self) -> f64 {
+        let dimension = self.state.dimension as usize;
+        let hash = self.state.next_dimension(self.seed);
+
+        match PRIMES.get(dimension) {
+            Some(&base) => {
+                let x = radical_inverse(base, self.state.index) + to_unit_f64(hash);
+                x.fract()
+            }
+            None => to_unit_f64(mix_bits(hash ^ u64::from(self.state.index))),
+        }
+    }
+
+    fn get_2d(&mut self) -> (f64, f64) {
+        let x = self.get_1d();
+        let y = self.get_1d();
+        (x, y)
+    }
+
+    fn clone_sampler(&self) -> Box<dyn Sampler> {
+        Box::new(self.clone())
+    }
+}
+
+/**
+ * Reflects the digits of `index`, written in base `base`, about the radix point.
+ */
+#[must_use]
+fn radical_inverse(base: u32, mut index: u32) -> f64 {
+    let inv_base = f64::from(base).recip();
+    let mut inv_base_n = 1.0;
+    let mut reversed_digits = 0.0;
+
+    while index > 0 {
+        let digit = index % base;
+        index /= base;
+        inv_base_n *= inv_base;
+        reversed_digits += f64::from(digit) * inv_base_n;
+    }
+
+    reversed_digits.min(1.0 - f64::EPSILON)
+}
@@ -0,0 +1,37 @@
+use super::Sampler;
+use rand::prelude::*;
+
+/**
+ * Type for a sampler that generates independent uniform random values.
+ */
+#[derive(Clone, Debug, Default)]
+pub struct IndependentSampler;
+
+impl IndependentSampler {
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+
+    #[must_use]
+    pub fn new_sampler() -> Box<dyn Sampler> {
+        Box::new(Self::new())
+    }
+}
+
+impl Sampler for IndependentSampler {
+    fn start_sample(&mut self, _pixel: (u32, u32), _index: u32, _samples_per_pixel: u32) {}
+
+    fn get_1d(&mut self) -> f64 {
+        thread_rng().gen()
+    }
+
+    fn get_2d(&mut self) -> (f64, f64) {
+        let mut rand_eng = thread_rng();
+        (rand_eng.gen(), rand_eng.gen())
+    }
+
+    fn clone_sampler(&self) -> Box<dyn Sampler> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,111 @@
+use super::{fraction_to_f64, permutation_element, SampleState, Sampler};
+
+/**
+ * Type for a sampler based on the Owen-scrambled Sobol sequence.
+ *
+ * Each pair of dimensions uses the first two dimensions of the Sobol sequence, with an
+ * independent Owen scrambling and ordering of the samples per pixel and dimension (a “padded”
+ * Sobol sampler).  This keeps every pair of dimensions well stratified without needing direction
+ * numbers for high dimensions.  The best distributions are obtained with a power of two samples
+ * per pixel.
+ */
+#[derive(Clone, Debug, Default)]
+pub struct SobolSampler {
+    seed: u64,
+    state: SampleState,
+}
+
+impl SobolSampler {
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            state: SampleState::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn new_sampler(seed: u64) -> Box<dyn Sampler> {
+        Box::new(Self::new(seed))
+    }
+
+    /**
+     * Returns the shuffled sample index for the current dimension, and the seeds for scrambling
+     * its two coordinates.
+     */
+    #[must_use]
+    fn next_dimension(&mut self) -> (u32, u32, u32) {
+        let hash = self.state.next_dimension(self.seed);
+        let index =
+            permutation_element(self.state.index, self.state.samples_per_pixel, hash as u32);
+        (
+            index,
+            (hash >> 32) as u32,
+            (hash >> 16) as u32 ^ hash as u32,
+        )
+    }
+}
+
+impl Sampler for SobolSampler {
+    fn start_sample(&mut self, pixel: (u32, u32), index: u32, samples_per_pixel: u32) {
+        self.state.start(pixel, index, samples_per_pixel);
+    }
+
+    fn get_1d(&mut self) -> f64 {
+        let (index, seed, _) = self.next_dimension();
+        fraction_to_f64(owen_scramble(sobol0(index), seed))
+    }
+
+    fn get_2d(&mut self) -> (f64, f64) {
+        let (index, seed0, seed1) = self.next_dimension();
+        (
+            fraction_to_f64(owen_scramble(sobol0(index), seed0)),
+            fraction_to_f64(owen_scramble(sobol1(index), seed1)),
+        )
+    }
+
+    fn clone_sampler(&self) -> Box<dyn Sampler> {
+        Box::new(self.clone())
+    }
+}
+
+/**
+ * Returns the first dimension of the Sobol sequence (the van der Corput sequence), as a 32-bit
+ * fixed-point fraction.
+ */
+#[must_use]
+fn sobol0(index: u32) -> u32 {
+    index.reverse_bits()
+}
+
+/**
+ * Returns the second dimension of the Sobol sequence, as a 32-bit fixed-point fraction.
+ */
+#[must_use]
+fn sobol1(mut index: u32) -> u32 {
+    let mut v = 1_u32 << 31;
+    let mut x = 0;
+    while index != 0 {
+        if index & 1 != 0 {
+            x ^= v;
+        }
+        index >>= 1;
+        v ^= v >> 1;
+    }
+    x
+}
+
+/**
+ * Applies a hash-based approximation of Owen scrambling to a 32-bit fixed-point fraction.
+ * (Burley, “Practical Hash-based Owen Scrambling”, 2020.)
+ */
+#[must_use]
+fn owen_scramble(mut v: u32, seed: u32) -> u32 {
+    v = v.reverse_bits();
+    v ^= v.wrapping_mul(0x3d20_adea);
+    v = v.wrapping_add(seed);
+    v = v.wrapping_mul((seed >> 16) | 1);
+    v ^= v.wrapping_mul(0x0552_6c56);
+    v ^= v.wrapping_mul(0x53a2_2864);
+    v.reverse_bits()
+}
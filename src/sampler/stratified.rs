@@ -0,0 +1,67 @@
+use super::{mix_bits, permutation_element, to_unit_f64, SampleState, Sampler};
+
+/**
+ * Type for a stratified (jittered) sampler.
+ *
+ * In each dimension, the samples of a pixel are assigned to distinct strata in a random order,
+ * and jittered within their strata.  Pairs of dimensions are stratified over a grid.
+ */
+#[derive(Clone, Debug, Default)]
+pub struct StratifiedSampler {
+    seed: u64,
+    state: SampleState,
+}
+
+impl StratifiedSampler {
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            state: SampleState::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn new_sampler(seed: u64) -> Box<dyn Sampler> {
+        Box::new(Self::new(seed))
+    }
+}
+
+impl Sampler for StratifiedSampler {
+    fn start_sample(&mut self, pixel: (u32, u32), index: u32, samples_per_pixel: u32) {
+        self.state.start(pixel, index, samples_per_pixel);
+    }
+
+    fn get_1d(&mut self) -> f64 {
+        let n = self.state.samples_per_pixel;
+        let hash = self.state.next_dimension(self.seed);
+
+        let stratum = permutation_element(self.state.index, n, hash as u32);
+        let jitter = to_unit_f64(mix_bits(hash ^ u64::from(self.state.index)));
+
+        (f64::from(stratum) + jitter) / f64::from(n)
+    }
+
+    fn get_2d(&mut self) -> (f64, f64) {
+        let n = self.state.samples_per_pixel;
+        let hash = self.state.next_dimension(self.seed);
+
+        // Use the smallest grid with at least one cell per sample.
+        let nx = (f64::from(n).sqrt().ceil() as u32).max(1);
+        let ny = n.div_ceil(nx);
+
+        let cell = permutation_element(self.state.index, nx * ny, hash as u32);
+        let jitter = mix_bits(hash ^ u64::from(self.state.index));
+        let jitter_x = to_unit_f64(jitter);
+        let jitter_y = to_unit_f64(mix_bits(jitter));
+
+        (
+            (f64::from(cell % nx) + jitter_x) / f64::from(nx),
+            (f64::from(cell / nx) + jitter_y) / f64::from(ny),
+        )
+    }
+
+    fn clone_sampler(&self) -> Box<dyn Sampler> {
+        Box::new(self.clone())
+    }
+}
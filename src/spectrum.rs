@@ -0,0 +1,82 @@
+mod sampled;
+mod upsampling;
+
+pub use sampled::SampledSpectrum;
+pub use upsampling::SigmoidPolynomial;
+
+/**
+ * Shortest wavelength of visible light considered, in nanometres.
+ */
+pub const WAVELENGTH_MIN: f64 = 360.0;
+
+/**
+ * Longest wavelength of visible light considered, in nanometres.
+ */
+pub const WAVELENGTH_MAX: f64 = 830.0;
+
+/**
+ * Number of wavelengths traced together by a ray when rendering spectrally.
+ */
+pub const SAMPLES: usize = 4;
+
+/**
+ * Returns the CIE 1931 2° standard observer colour matching functions x̄, ȳ and z̄ at
+ * `wavelength`, in nanometres, using the multi-lobe Gaussian fit of Wyman, Sloan and Shirley
+ * (“Simple Analytic Approximations to the CIE XYZ Color Matching Functions”, 2013).
+ */
+#[must_use]
+pub fn cie_xyz(wavelength: f64) -> (f64, f64, f64) {
+    let g = |mu: f64, sigma1: f64, sigma2: f64| {
+        let t = (wavelength - mu) / if wavelength < mu { sigma1 } else { sigma2 };
+        (-0.5 * t * t).exp()
+    };
+
+    let x =
+        1.056 * g(599.8, 37.9, 31.0) + 0.362 * g(442.0, 16.0, 26.7) - 0.065 * g(501.1, 20.4, 26.2);
+    let y = 0.821 * g(568.8, 46.9, 40.5) + 0.286 * g(530.9, 16.3, 31.1);
+    let z = 1.217 * g(437.0, 11.8, 36.0) + 0.681 * g(459.0, 26.0, 13.8);
+    (x, y, z)
+}
+
+/**
+ * Returns the spectral radiance of a black body at `temperature`, in kelvins, and `wavelength`,
+ * in nanometres, by Planck’s law.  The result is in W/(sr·m²) per nanometre.
+ */
+#[must_use]
+pub fn planck(wavelength: f64, temperature: f64) -> f64 {
+    const H: f64 = 6.626_070_15e-34; // Planck constant.
+    const C: f64 = 299_792_458.0; // Speed of light.
+    const K: f64 = 1.380_649e-23; // Boltzmann constant.
+
+    let lambda = wavelength * 1e-9;
+    let radiance =
+        2.0 * H * C * C / (lambda.powi(5) * ((H * C / (lambda * K * temperature)).exp_m1()));
+    radiance * 1e-9
+}
+
+/**
+ * Samples a wavelength, in nanometres, between `WAVELENGTH_MIN` and `WAVELENGTH_MAX` from `u` in
+ * [0, 1), concentrated where the eye is most sensitive (after Radziszewski et al.).  Returns the
+ * wavelength and its probability density per nanometre.
+ */
+#[must_use]
+pub fn sample_wavelength(u: f64) -> (f64, f64) {
+    let wavelength = 538.0 - 138.888_889 * (0.856_910_62 - 1.827_501_97 * u).atanh();
+    let pdf = 0.003_939_804_2 / (0.0072 * (wavelength - 538.0)).cosh().powi(2);
+    (wavelength, pdf)
+}
+
+/**
+ * Samples `SAMPLES` wavelengths from `u` in [0, 1), as `sample_wavelength` does for `u` and for
+ * `u` rotated by equal steps around [0, 1).  The first is the hero wavelength.  Returns the
+ * wavelengths and their probability densities.
+ */
+#[must_use]
+pub fn sample_wavelengths(u: f64) -> ([f64; SAMPLES], [f64; SAMPLES]) {
+    let mut wavelengths = [0.0; SAMPLES];
+    let mut pdfs = [0.0; SAMPLES];
+    for (i, (wavelength, pdf)) in wavelengths.iter_mut().zip(&mut pdfs).enumerate() {
+        (*wavelength, *pdf) = sample_wavelength((u + i as f64 / SAMPLES as f64).fract());
+    }
+    (wavelengths, pdfs)
+}
@@ -0,0 +1,95 @@
+use super::{SigmoidPolynomial, SAMPLES};
+use crate::Colour;
+use std::ops;
+
+/**
+ * Type for the values of a spectrum, such as spectral radiance or the fraction of light
+ * transmitted, at the wavelengths carried by a ray.
+ */
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SampledSpectrum(pub [f64; SAMPLES]);
+
+impl SampledSpectrum {
+    /**
+     * Creates the spectrum of `colour` at `wavelengths`, in nanometres.
+     *
+     * The colour is upsampled to a smooth spectrum with the same colour.  Colours brighter than
+     * white, as of light sources, are scaled down into range first and the spectrum scaled back
+     * up.
+     */
+    #[must_use]
+    pub fn new(colour: Colour, wavelengths: [f64; SAMPLES]) -> Self {
+        let max = colour.0.max(colour.1).max(colour.2);
+        if max <= 0.0 {
+            return Self::default();
+        }
+
+        let scale = if max > 1.0 { 2.0 * max } else { 1.0 };
+        let spectrum = SigmoidPolynomial::new(colour / scale);
+        Self(wavelengths.map(|wavelength| scale * spectrum.value(wavelength)))
+    }
+
+    /**
+     * Returns the colour of the spectrum, as an estimate from its values at `wavelengths`, chosen
+     * with probability densities `pdfs`.  Each wavelength is projected onto CIE XYZ by the colour
+     * matching functions and converted to linear sRGB, with white light of equal energy at all
+     * wavelengths giving white.
+     */
+    #[must_use]
+    pub fn to_colour(self, wavelengths: [f64; SAMPLES], pdfs: [f64; SAMPLES]) -> Colour {
+        let mut colour = Colour(0.0, 0.0, 0.0);
+        for ((&value, wavelength), pdf) in self.0.iter().zip(wavelengths).zip(pdfs) {
+            if value != 0.0 && pdf > 0.0 {
+                colour += Colour::new_monochromatic(wavelength) * (value / pdf);
+            }
+        }
+        colour / SAMPLES as f64
+    }
+
+    /**
+     * Returns the spectrum with only its value at the first, hero, wavelength, weighted to stand
+     * in for all the wavelengths.  This is used once a path has scattered in a direction that
+     * only suits the hero wavelength.
+     */
+    #[must_use]
+    pub fn hero(self) -> Self {
+        let mut values = [0.0; SAMPLES];
+        values[0] = self.0[0] * SAMPLES as f64;
+        Self(values)
+    }
+}
+
+impl ops::Add for SampledSpectrum {
+    type Output = Self;
+    fn add(self, s: Self) -> Self::Output {
+        let mut values = self.0;
+        for (v, s) in values.iter_mut().zip(s.0) {
+            *v += s;
+        }
+        Self(values)
+    }
+}
+
+impl ops::Mul for SampledSpectrum {
+    type Output = Self;
+    fn mul(self, s: Self) -> Self::Output {
+        let mut values = self.0;
+        for (v, s) in values.iter_mut().zip(s.0) {
+            *v *= s;
+        }
+        Self(values)
+    }
+}
+
+impl ops::Mul<f64> for SampledSpectrum {
+    type Output = Self;
+    fn mul(self, s: f64) -> Self::Output {
+        Self(self.0.map(|v| v * s))
+    }
+}
+
+impl ops::AddAssign for SampledSpectrum {
+    fn add_assign(&mut self, s: Self) {
+        *self = *self + s;
+    }
+}
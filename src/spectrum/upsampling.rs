@@ -0,0 +1,312 @@
+use super::{WAVELENGTH_MAX, WAVELENGTH_MIN};
+use crate::Colour;
+use std::sync::OnceLock;
+
+/**
+ * Number of entries along each axis of the table of coefficients.
+ */
+const RESOLUTION: usize = 32;
+
+/**
+ * Spacing, in nanometres, of the wavelengths at which fitted spectra are integrated.
+ */
+const STEP: f64 = 5.0;
+
+/**
+ * Type for a smooth spectrum with values in [0, 1], given by a sigmoid of a quadratic in the
+ * wavelength (Jakob and Hanika, “A Low-Dimensional Function Space for Efficient Spectral
+ * Upsampling”, 2019).  The wavelength is normalised to [0, 1] over the visible range.
+ */
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SigmoidPolynomial {
+    c0: f64,
+    c1: f64,
+    c2: f64,
+}
+
+impl SigmoidPolynomial {
+    /**
+     * Creates the smooth spectrum whose colour is `colour`, which should have components in
+     * [0, 1].  Colours outside the range of such spectra get the nearest spectrum found.
+     */
+    #[must_use]
+    pub fn new(colour: Colour) -> Self {
+        let rgb = [
+            colour.0.clamp(0.0, 1.0),
+            colour.1.clamp(0.0, 1.0),
+            colour.2.clamp(0.0, 1.0),
+        ];
+
+        // Greys are constant spectra.
+        if rgb[0] == rgb[1] && rgb[1] == rgb[2] {
+            let v = rgb[0];
+            return Self {
+                c0: 0.0,
+                c1: 0.0,
+                c2: (v - 0.5) / (v * (1.0 - v)).sqrt(),
+            };
+        }
+
+        table().lookup(rgb)
+    }
+
+    /**
+     * Returns the value of the spectrum at `wavelength`, in nanometres.
+     */
+    #[must_use]
+    pub fn value(self, wavelength: f64) -> f64 {
+        let t = (wavelength - WAVELENGTH_MIN) / (WAVELENGTH_MAX - WAVELENGTH_MIN);
+        sigmoid(self.c0.mul_add(t, self.c1).mul_add(t, self.c2))
+    }
+}
+
+/**
+ * Maps the real line smoothly onto (0, 1), including the infinities of constant black and white
+ * spectra.
+ */
+fn sigmoid(x: f64) -> f64 {
+    if x.is_infinite() {
+        return if x > 0.0 { 1.0 } else { 0.0 };
+    }
+    0.5 + x / (2.0 * x.mul_add(x, 1.0).sqrt())
+}
+
+/**
+ * Type for the table of fitted coefficients, indexed by the largest colour component, then by its
+ * value and the values of the other two components relative to it.
+ */
+#[derive(Debug)]
+struct Table {
+    /**
+     * Values of the largest component at the entries along the third axis, which are closer
+     * together near black and white.
+     */
+    scale: [f64; RESOLUTION],
+
+    coefficients: Box<[[f64; 3]]>,
+}
+
+/**
+ * Returns the table of coefficients, fitting it the first time it is needed.
+ */
+fn table() -> &'static Table {
+    static TABLE: OnceLock<Table> = OnceLock::new();
+    TABLE.get_or_init(Table::new)
+}
+
+impl Table {
+    /**
+     * Fits the coefficients at every entry of the table.  Each fit starts from the solution at
+     * the neighbouring entry, walking outwards from a mid-grey, so that the saturated colours at
+     * the edges are reached gradually.
+     */
+    fn new() -> Self {
+        let smoothstep = |x: f64| x * x * (3.0 - 2.0 * x);
+        let mut scale = [0.0; RESOLUTION];
+        for (k, s) in scale.iter_mut().enumerate() {
+            *s = smoothstep(smoothstep(k as f64 / (RESOLUTION - 1) as f64));
+        }
+
+        let weights = weights();
+        let mut coefficients = vec![[0.0; 3]; 3 * RESOLUTION.pow(3)].into_boxed_slice();
+        let start = RESOLUTION / 5;
+        for l in 0..3 {
+            for j in 0..RESOLUTION {
+                let y = j as f64 / (RESOLUTION - 1) as f64;
+                for i in 0..RESOLUTION {
+                    let x = i as f64 / (RESOLUTION - 1) as f64;
+                    let mut fit = |ks: &mut dyn Iterator<Item = usize>| {
+                        let mut c = [0.0; 3];
+                        for k in ks {
+                            let b = scale[k];
+                            let mut rgb = [0.0; 3];
+                            rgb[l] = b;
+                            rgb[(l + 1) % 3] = x * b;
+                            rgb[(l + 2) % 3] = y * b;
+                            gauss_newton(rgb, &mut c, &weights);
+                            coefficients[index(l, k, j, i)] = c;
+                        }
+                    };
+                    fit(&mut (start..RESOLUTION));
+                    fit(&mut (0..start).rev());
+                }
+            }
+        }
+
+        Self {
+            scale,
+            coefficients,
+        }
+    }
+
+    /**
+     * Interpolates the coefficients for `rgb`, which must not be a grey.
+     */
+    fn lookup(&self, rgb: [f64; 3]) -> SigmoidPolynomial {
+        let l = if rgb[0] >= rgb[1] && rgb[0] >= rgb[2] {
+            0
+        } else if rgb[1] >= rgb[2] {
+            1
+        } else {
+            2
+        };
+        let z = rgb[l];
+        let n = (RESOLUTION - 1) as f64;
+        let x = rgb[(l + 1) % 3] * n / z;
+        let y = rgb[(l + 2) % 3] * n / z;
+
+        let k = self
+            .scale
+            .partition_point(|&s| s <= z)
+            .clamp(1, RESOLUTION - 1)
+            - 1;
+        let tz = (z - self.scale[k]) / (self.scale[k + 1] - self.scale[k]);
+        let i = (x as usize).min(RESOLUTION - 2);
+        let j = (y as usize).min(RESOLUTION - 2);
+        let tx = x - i as f64;
+        let ty = y - j as f64;
+
+        let mut c = [0.0; 3];
+        for (dk, wk) in [(0, 1.0 - tz), (1, tz)] {
+            for (dj, wj) in [(0, 1.0 - ty), (1, ty)] {
+                for (di, wi) in [(0, 1.0 - tx), (1, tx)] {
+                    let entry = self.coefficients[index(l, k + dk, j + dj, i + di)];
+                    for (c, e) in c.iter_mut().zip(entry) {
+                        *c += wk * wj * wi * e;
+                    }
+                }
+            }
+        }
+
+        SigmoidPolynomial {
+            c0: c[0],
+            c1: c[1],
+            c2: c[2],
+        }
+    }
+}
+
+/**
+ * Returns the position in the table of the entry for largest component `l`, with the largest
+ * component at entry `k` of the scale and the others at entries `j` and `i`.
+ */
+const fn index(l: usize, k: usize, j: usize, i: usize) -> usize {
+    ((l * RESOLUTION + k) * RESOLUTION + j) * RESOLUTION + i
+}
+
+/**
+ * Returns the normalised wavelengths at which fitted spectra are integrated, with the colour of
+ * light at each, so that a spectrum’s colour is the sum of its values times their colours.
+ */
+fn weights() -> Vec<(f64, Colour)> {
+    let count = ((WAVELENGTH_MAX - WAVELENGTH_MIN) / STEP) as usize;
+    (0..=count)
+        .map(|n| {
+            let wavelength = STEP.mul_add(n as f64, WAVELENGTH_MIN);
+            let t = (wavelength - WAVELENGTH_MIN) / (WAVELENGTH_MAX - WAVELENGTH_MIN);
+            (t, STEP * Colour::new_monochromatic(wavelength))
+        })
+        .collect()
+}
+
+/**
+ * Refines the coefficients `c` by Gauss–Newton iteration, so that the colour of their spectrum
+ * approaches `rgb`.  Steps are shortened until they reduce the error, which keeps the iteration
+ * stable for colours that smooth spectra cannot quite reach.
+ */
+fn gauss_newton(rgb: [f64; 3], c: &mut [f64; 3], weights: &[(f64, Colour)]) {
+    let error = |r: [f64; 3]| r.iter().map(|r| r * r).sum::<f64>();
+
+    for _ in 0..15 {
+        let (residual, jacobian) = residual(rgb, *c, weights);
+        let current = error(residual);
+        if current < 1e-12 {
+            break;
+        }
+        let step = match solve(jacobian, residual) {
+            Some(step) => step,
+            None => break,
+        };
+
+        let mut length = 1.0;
+        let improved = loop {
+            let next = [
+                length * step[0] + c[0],
+                length * step[1] + c[1],
+                length * step[2] + c[2],
+            ];
+            if error(residual_only(rgb, next, weights)) < current {
+                break Some(next);
+            }
+            length *= 0.5;
+            if length < 1e-3 {
+                break None;
+            }
+        };
+        match improved {
+            Some(next) => *c = next,
+            None => break,
+        }
+    }
+}
+
+/**
+ * Returns the difference between `rgb` and the colour of the spectrum with coefficients `c`, and
+ * the derivatives of the colour with respect to the coefficients.
+ */
+fn residual(rgb: [f64; 3], c: [f64; 3], weights: &[(f64, Colour)]) -> ([f64; 3], [[f64; 3]; 3]) {
+    let mut residual = rgb;
+    let mut jacobian = [[0.0; 3]; 3];
+    for &(t, w) in weights {
+        let x = c[0].mul_add(t, c[1]).mul_add(t, c[2]);
+        let s = sigmoid(x);
+        let ds = 0.5 / x.mul_add(x, 1.0).powf(1.5);
+        for (ch, &w) in [w.0, w.1, w.2].iter().enumerate() {
+            residual[ch] -= s * w;
+            jacobian[ch][0] += ds * t * t * w;
+            jacobian[ch][1] += ds * t * w;
+            jacobian[ch][2] += ds * w;
+        }
+    }
+    (residual, jacobian)
+}
+
+/**
+ * Returns the difference between `rgb` and the colour of the spectrum with coefficients `c`.
+ */
+fn residual_only(rgb: [f64; 3], c: [f64; 3], weights: &[(f64, Colour)]) -> [f64; 3] {
+    let mut residual = rgb;
+    for &(t, w) in weights {
+        let s = sigmoid(c[0].mul_add(t, c[1]).mul_add(t, c[2]));
+        residual[0] -= s * w.0;
+        residual[1] -= s * w.1;
+        residual[2] -= s * w.2;
+    }
+    residual
+}
+
+/**
+ * Solves the 3 × 3 linear system `a` x = `b` by Cramer’s rule, or returns `None` if it is
+ * singular.
+ */
+fn solve(a: [[f64; 3]; 3], b: [f64; 3]) -> Option<[f64; 3]> {
+    let det = |m: [[f64; 3]; 3]| {
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    };
+    let d = det(a);
+    if d.abs() < 1e-15 {
+        return None;
+    }
+
+    let mut x = [0.0; 3];
+    for (col, x) in x.iter_mut().enumerate() {
+        let mut m = a;
+        for (row, b) in b.iter().enumerate() {
+            m[row][col] = *b;
+        }
+        *x = det(m) / d;
+    }
+    Some(x)
+}
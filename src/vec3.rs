@@ -1,5 +1,6 @@
 use rand::{distributions::Uniform, prelude::*};
 use std::{
+    f64::consts,
     fmt,
     ops::{self, Range},
 };
@@ -81,6 +82,51 @@ impl Vec3 {
         }
     }
 
+    /**
+     * Maps a uniform sample in [0, 1)² to a unit vector, uniformly distributed over the sphere.
+     */
+    #[must_use]
+    pub fn new_unit_from_sample(sample: (f64, f64)) -> Self {
+        let (u0, u1) = sample;
+        let z = 1.0 - 2.0 * u0;
+        let r = (1.0 - z * z).max(0.0).sqrt();
+        let (sin_phi, cos_phi) = (consts::TAU * u1).sin_cos();
+
+        Vec3(r * cos_phi, r * sin_phi, z)
+    }
+
+    /**
+     * Maps a uniform sample in [0, 1)³ to a vector uniformly distributed inside a unit sphere.
+     * The pair selects the direction and the last coordinate the distance from the centre.
+     */
+    #[must_use]
+    pub fn new_in_unit_sphere_from_sample(sample: (f64, f64), radius_sample: f64) -> Self {
+        radius_sample.cbrt() * Self::new_unit_from_sample(sample)
+    }
+
+    /**
+     * Maps a uniform sample in [0, 1)² to a vector uniformly distributed inside a unit disk, by
+     * Shirley and Chiu’s concentric mapping, which preserves the stratification of the sample.
+     */
+    #[must_use]
+    pub fn new_in_unit_disk_from_sample(sample: (f64, f64)) -> Self {
+        let a = 2.0 * sample.0 - 1.0;
+        let b = 2.0 * sample.1 - 1.0;
+
+        if a == 0.0 && b == 0.0 {
+            return Vec3(0.0, 0.0, 0.0);
+        }
+
+        let (r, theta) = if a.abs() > b.abs() {
+            (a, consts::FRAC_PI_4 * (b / a))
+        } else {
+            (b, consts::FRAC_PI_2 - consts::FRAC_PI_4 * (a / b))
+        };
+        let (sin_theta, cos_theta) = theta.sin_cos();
+
+        Vec3(r * cos_theta, r * sin_theta, 0.0)
+    }
+
     #[must_use]
     pub const fn x(self) -> f64 {
         self.0